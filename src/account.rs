@@ -3,6 +3,7 @@ use cosmrs::{
     AccountId,
 };
 use cosmwasm_std::HumanAddr;
+use sha2::{Digest, Sha256, Sha512};
 
 use crate::{
     consts,
@@ -36,6 +37,11 @@ impl Account {
         self.id().as_ref().into()
     }
 
+    /// The key others build a [`Multisig`] with.
+    pub fn public_key(&self) -> PublicKey {
+        self.pubk
+    }
+
     pub(crate) fn signing_key(&self) -> SigningKey {
         SigningKey::from(&self.prvk)
     }
@@ -51,6 +57,145 @@ impl Account {
     }
 }
 
+/// A legacy Amino multisig key: txs from its address need signatures of `threshold` members.
+///
+/// Contract messages sent from a multisig are encrypted with an account derived from the
+/// multisig key, so a multisig built again from the same members encrypts the same way. Anyone
+/// who knows the members can derive it too, use [`Multisig::with_encryption_account`] to keep
+/// the messages private. [`Client::broadcast_raw`](crate::Client::broadcast_raw) returns
+/// contract responses as ciphertext, [`Client::decrypt_msg_response`](crate::Client::decrypt_msg_response)
+/// decrypts them with the encryption account.
+#[derive(Clone)]
+pub struct Multisig {
+    threshold: u32,
+    members: Vec<PublicKey>,
+    encryption_account: Account,
+}
+
+impl Multisig {
+    /// The members keep their order, as with `secretd keys add --multisig --nosort`, and must
+    /// be secp256k1 keys.
+    pub fn new(threshold: u32, members: &[PublicKey]) -> crate::Result<Multisig> {
+        if threshold == 0 || threshold as usize > members.len() {
+            return Err(crate::Error::Multisig(format!(
+                "threshold {} is not between 1 and the {} members",
+                threshold,
+                members.len()
+            )));
+        }
+        for pubk in members {
+            let type_url = pubk.to_any()?.type_url;
+            if type_url != SECP256K1_TYPE_URL {
+                return Err(crate::Error::Multisig(format!(
+                    "member key {type_url} is not a secp256k1 key"
+                )));
+            }
+        }
+
+        let mut seed = [0; 64];
+        seed.copy_from_slice(&Sha512::digest(amino_bytes(threshold, members)));
+        Ok(Multisig {
+            threshold,
+            members: members.to_vec(),
+            encryption_account: Account::from_seed(seed),
+        })
+    }
+
+    /// Encrypts contract messages with `account` instead of the account derived from the key.
+    /// It is not part of the multisig key, so the members need not know it.
+    pub fn with_encryption_account(mut self, account: &Account) -> Multisig {
+        self.encryption_account = account.clone();
+        self
+    }
+
+    /// Reads the `LegacyAminoPubKey` of a multisig, as written by [`Multisig::public_key_any`].
+    pub(crate) fn from_public_key_any(any: cosmrs::Any) -> crate::Result<Multisig> {
+        use cosmrs::proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+        use prost::Message;
+
+        let pubk = LegacyAminoPubKey::decode(any.value.as_slice())?;
+        let members = pubk
+            .public_keys
+            .into_iter()
+            .map(PublicKey::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Multisig::new(pubk.threshold, &members)
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn human_address(&self) -> HumanAddr {
+        self.id().as_ref().into()
+    }
+
+    pub(crate) fn id(&self) -> AccountId {
+        let hash = Sha256::digest(amino_bytes(self.threshold, &self.members));
+        AccountId::new(consts::CHAIN_PREFIX, &hash[..20]).expect("invalid multisig address")
+    }
+
+    /// The position of `account` among the members.
+    pub(crate) fn member_index(&self, account: &Account) -> Option<usize> {
+        self.members.iter().position(|pubk| *pubk == account.pubk)
+    }
+
+    pub(crate) fn members(&self) -> usize {
+        self.members.len()
+    }
+
+    /// The account contract messages are encrypted with.
+    pub(crate) fn encryption_account(&self) -> &Account {
+        &self.encryption_account
+    }
+
+    /// The `LegacyAminoPubKey` of the multisig as a protobuf `Any`.
+    pub(crate) fn public_key_any(&self) -> crate::Result<cosmrs::Any> {
+        use cosmrs::proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+        use prost::Message;
+
+        let public_keys = self
+            .members
+            .iter()
+            .map(|pubk| pubk.to_any())
+            .collect::<Result<_, _>>()?;
+        let pubk = LegacyAminoPubKey {
+            threshold: self.threshold,
+            public_keys,
+        };
+        Ok(cosmrs::Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_owned(),
+            value: pubk.encode_to_vec(),
+        })
+    }
+}
+
+const SECP256K1_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// The Amino binary encoding of a multisig key, which its address is the truncated hash of.
+/// The prefixes are those Amino derives from the names `tendermint/PubKeyMultisigThreshold`
+/// and `tendermint/PubKeySecp256k1`.
+fn amino_bytes(threshold: u32, members: &[PublicKey]) -> Vec<u8> {
+    const MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+    const SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+    let mut bytes = MULTISIG_PREFIX.to_vec();
+    // field 1, varint
+    bytes.push(0x08);
+    prost::encoding::encode_varint(threshold.into(), &mut bytes);
+    for pubk in members {
+        let key = pubk.to_bytes();
+        let mut member = SECP256K1_PREFIX.to_vec();
+        prost::encoding::encode_varint(key.len() as u64, &mut member);
+        member.extend(key);
+        // field 2, length delimited
+        bytes.push(0x12);
+        prost::encoding::encode_varint(member.len() as u64, &mut bytes);
+        bytes.extend(member);
+    }
+    bytes
+}
+
 pub fn a() -> Account {
     Account::from_mnemonic(A_MNEMONIC).unwrap()
 }
@@ -90,6 +235,79 @@ mod test {
             HumanAddr::from("secret1ldjxljw7v4vk6zhyduywh04hpj0jdwxsmrlatf")
         );
     }
+
+    #[test]
+    fn multisig_address_depends_on_members_and_threshold() {
+        let (a, b, c) = (a().public_key(), b().public_key(), c().public_key());
+        let multisig = Multisig::new(2, &[a, b, c]).unwrap();
+        assert!(multisig.human_address().as_str().starts_with("secret1"));
+        assert_eq!(multisig.member_index(&super::b()), Some(1));
+        assert_eq!(multisig.member_index(&d()), None);
+        assert_ne!(
+            Multisig::new(1, &[a, b, c]).unwrap().human_address(),
+            multisig.human_address()
+        );
+        assert_ne!(
+            Multisig::new(2, &[b, a, c]).unwrap().human_address(),
+            multisig.human_address()
+        );
+
+        let imported = Multisig::from_public_key_any(multisig.public_key_any().unwrap()).unwrap();
+        assert_eq!(imported.human_address(), multisig.human_address());
+
+        assert!(Multisig::new(0, &[a, b]).is_err());
+        assert!(Multisig::new(3, &[a, b]).is_err());
+    }
+
+    #[test]
+    fn multisig_address_vector() {
+        // 2 of a, b and c in this order, the address of
+        // `secretd keys add --multisig a,b,c --multisig-threshold 2 --nosort`
+        let members = [a().public_key(), b().public_key(), c().public_key()];
+        assert_eq!(
+            Multisig::new(2, &members).unwrap().human_address(),
+            HumanAddr::from("secret16nn3jr00tw2w0ehv03p3whhpeeqf3jfqq4h0ck")
+        );
+    }
+
+    #[test]
+    fn multisig_encryption_account() {
+        let members = [a().public_key(), b().public_key(), c().public_key()];
+        let multisig = Multisig::new(2, &members).unwrap();
+        let rebuilt = Multisig::from_public_key_any(multisig.public_key_any().unwrap()).unwrap();
+        assert_eq!(
+            rebuilt.encryption_account().public_key(),
+            multisig.encryption_account().public_key()
+        );
+        assert_ne!(
+            Multisig::new(1, &members)
+                .unwrap()
+                .encryption_account()
+                .public_key(),
+            multisig.encryption_account().public_key()
+        );
+
+        let private = multisig.with_encryption_account(&d());
+        assert_eq!(private.encryption_account().public_key(), d().public_key());
+        assert_eq!(
+            private.human_address(),
+            rebuilt.human_address(),
+            "the encryption account is not part of the key"
+        );
+    }
+
+    #[test]
+    fn multisig_members_are_secp256k1_keys() {
+        // the compressed ed25519 base point
+        let mut point = [0x66; 32];
+        point[0] = 0x58;
+        let ed25519 =
+            PublicKey::from(cosmrs::tendermint::PublicKey::from_raw_ed25519(&point).unwrap());
+        assert!(matches!(
+            Multisig::new(1, &[a().public_key(), ed25519]),
+            Err(crate::Error::Multisig(_))
+        ));
+    }
 }
 
 static A_MNEMONIC: &str = "grant rice replace explain federal release fix clever romance raise often wild taxi quarter soccer fiber love must tape steak together observe swap guitar";
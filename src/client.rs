@@ -1,6 +1,12 @@
-use std::cell::RefCell;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
 
-use cosmrs::rpc::{self, Client as RpcClient};
+use cosmrs::{
+    rpc::{self, Client as RpcClient},
+    tendermint::chain,
+};
 use tokio::runtime::Runtime;
 
 use crate::{
@@ -9,6 +15,11 @@ use crate::{
     CodeHash, Error, Result,
 };
 
+use tx::builder::BroadcastMode;
+use types::{FeeProfile, GasPrice, NodeInfo};
+
+// the client block impl
+mod block;
 // the client query impl
 mod query;
 // the client tx impl
@@ -19,10 +30,16 @@ pub struct Client {
     rt: Runtime,
     rpc: rpc::HttpClient,
     enclave_pubk: RefCell<Option<crypto::Key>>,
+    expected_chain_id: Option<chain::Id>,
+    chain_id: RefCell<Option<chain::Id>>,
+    gas_price: RefCell<GasPrice>,
+    broadcast_mode: Cell<BroadcastMode>,
+    /// The next sequence of every account that signed a tx, by address.
+    sequences: RefCell<HashMap<String, tx::Sequence>>,
 }
 
 impl Client {
-    pub(crate) fn init(rpc_host: &str, rpc_port: u16) -> Result<Client> {
+    pub(crate) fn init(rpc_host: &str, rpc_port: u16, chain_id: Option<&str>) -> Result<Client> {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
@@ -31,11 +48,19 @@ impl Client {
         let rpc_url = format!("http://{}:{}", rpc_host, rpc_port);
         let rpc = rpc::HttpClient::new(rpc_url.as_str())?;
         let enclave_pubk = RefCell::default();
+        let expected_chain_id = chain_id
+            .map(|id| id.parse().map_err(|_| Error::InvalidChainId(id.to_owned())))
+            .transpose()?;
 
         Ok(Client {
             rt,
             rpc,
             enclave_pubk,
+            expected_chain_id,
+            chain_id: RefCell::default(),
+            gas_price: RefCell::new(FeeProfile::Default.gas_price()),
+            broadcast_mode: Cell::new(BroadcastMode::Commit),
+            sequences: RefCell::default(),
         })
     }
 
@@ -45,7 +70,69 @@ impl Client {
 
     pub fn last_block_height(&self) -> Result<u32> {
         let res = self.block_on(rpc::Client::latest_block(&self.rpc))?;
-        Ok(res.block.header.height.value() as _)
+        let height = res.block.header.height.value();
+        u32::try_from(height).map_err(|_| Error::InvalidHeight(height))
+    }
+
+    pub fn node_info(&self) -> Result<NodeInfo> {
+        let status = self.block_on(self.rpc.status())?;
+        let abci_info = self.block_on(self.rpc.abci_info())?;
+        Ok(NodeInfo {
+            chain_id: status.node_info.network.to_string(),
+            moniker: status.node_info.moniker.to_string(),
+            node_version: status.node_info.version.to_string(),
+            app_name: abci_info.data,
+            app_version: abci_info.version,
+            latest_block_height: status.sync_info.latest_block_height.value(),
+            catching_up: status.sync_info.catching_up,
+        })
+    }
+
+    /// The chain id used to sign transactions, fetched from the node on first use and checked
+    /// against the configured chain id, if any.
+    pub(crate) fn chain_id(&self) -> Result<chain::Id> {
+        if let Some(chain_id) = self.chain_id.borrow().as_ref() {
+            return Ok(chain_id.clone());
+        }
+
+        let status = self.block_on(self.rpc.status())?;
+        let chain_id = status.node_info.network;
+
+        if let Some(expected) = &self.expected_chain_id {
+            if expected != &chain_id {
+                return Err(Error::ChainIdMismatch(
+                    expected.to_string(),
+                    chain_id.to_string(),
+                ));
+            }
+        }
+
+        self.chain_id.replace(Some(chain_id.clone()));
+
+        Ok(chain_id)
+    }
+
+    /// The gas price fees are computed with, unless a tx sets its own.
+    pub fn gas_price(&self) -> GasPrice {
+        self.gas_price.borrow().clone()
+    }
+
+    pub fn set_gas_price(&self, gas_price: GasPrice) {
+        self.gas_price.replace(gas_price);
+    }
+
+    pub fn set_fee_profile(&self, profile: FeeProfile) {
+        self.set_gas_price(profile.gas_price());
+    }
+
+    pub fn broadcast_mode(&self) -> BroadcastMode {
+        self.broadcast_mode.get()
+    }
+
+    /// Sets how [`Tx::broadcast`](crate::Tx::broadcast) sends txs. In sync and async mode it
+    /// polls for the tx instead of holding a `broadcast_tx_commit` request open.
+    pub fn set_broadcast_mode(&self, mode: BroadcastMode) {
+        self.broadcast_mode.set(mode);
     }
 
     fn enclave_public_key(&self) -> Result<crypto::Key> {
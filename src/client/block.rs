@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use cosmrs::{
+    proto::tendermint::google::protobuf::Timestamp, rpc::Client, tendermint::block::Height,
+};
+
+use crate::{client::types::BlockInfo, Error, Result};
+
+use super::query::system_time;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+impl super::Client {
+    pub fn block(&self, height: u64) -> Result<BlockInfo> {
+        let res = self.block_on(self.rpc.block(to_height(height)?))?;
+        Ok(block_info(res.block_id, res.block))
+    }
+
+    pub fn latest_block(&self) -> Result<BlockInfo> {
+        let res = self.block_on(self.rpc.latest_block())?;
+        Ok(block_info(res.block_id, res.block))
+    }
+
+    pub fn latest_block_time(&self) -> Result<SystemTime> {
+        self.latest_block().map(|block| block.time)
+    }
+
+    /// Blocks until the chain reaches `height`, returning the first block at or above it.
+    pub fn wait_for_height(&self, height: u64) -> Result<BlockInfo> {
+        self.wait_for_block(|block| block.height >= height)
+    }
+
+    /// Blocks until `n` more blocks have been produced.
+    pub fn wait_blocks(&self, n: u64) -> Result<BlockInfo> {
+        let height = self.latest_block()?.height;
+        self.wait_for_height(height + n)
+    }
+
+    /// Blocks until the chain produces a block with a timestamp at or after `time`.
+    pub fn wait_until_time(&self, time: SystemTime) -> Result<BlockInfo> {
+        self.wait_for_block(|block| block.time >= time)
+    }
+
+    fn wait_for_block<F>(&self, done: F) -> Result<BlockInfo>
+    where
+        F: Fn(&BlockInfo) -> bool,
+    {
+        let mut last_height = 0;
+        let mut last_progress = Instant::now();
+
+        loop {
+            let block = self.latest_block()?;
+
+            if done(&block) {
+                return Ok(block);
+            }
+
+            if block.height > last_height {
+                last_height = block.height;
+                last_progress = Instant::now();
+            } else if last_progress.elapsed() > STALL_TIMEOUT {
+                return Err(Error::ChainStalled(last_height, STALL_TIMEOUT.as_secs()));
+            }
+
+            self.block_on(tokio::time::sleep(POLL_INTERVAL));
+        }
+    }
+}
+
+pub(crate) fn to_height(height: u64) -> Result<Height> {
+    Height::try_from(height).map_err(|_| Error::InvalidHeight(height))
+}
+
+fn block_info(id: cosmrs::tendermint::block::Id, block: cosmrs::tendermint::Block) -> BlockInfo {
+    BlockInfo {
+        height: block.header.height.value(),
+        hash: id.hash.to_string(),
+        time: {
+            let ts = Timestamp::from(block.header.time);
+            system_time(ts.seconds, ts.nanos)
+        },
+        chain_id: block.header.chain_id.to_string(),
+        num_txs: block.data.iter().count(),
+    }
+}
@@ -1,23 +1,105 @@
-use cosmrs::rpc::{endpoint::abci_query::AbciQuery as QueryResponse, Client};
+use cosmrs::{
+    proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse},
+    rpc::{endpoint::abci_query::AbciQuery as QueryResponse, Client},
+    AccountId,
+};
 use prost::Message;
 
 use crate::{account::Account, CodeHash, CodeId, Contract, Error, Result};
 
-use super::types::AccountInfo;
+// the client governance query impl
+mod gov;
+// the client historical query impl
+mod height;
+// the client staking and distribution query impl
+mod staking;
+
+use super::types::{AccountInfo, AccountKind, CodeInfo, ContractInfo, Page, Pagination, TxInfo};
 
 impl super::Client {
     pub fn query_uscrt_balance(&self, wallet: &str) -> Result<cosmwasm_std::Uint128> {
+        self.query_balance(wallet, crate::consts::COIN_DENOM)
+            .map(|coin| coin.amount)
+    }
+
+    pub fn query_balance(&self, address: &str, denom: &str) -> Result<cosmwasm_std::Coin> {
+        self.query_balance_at(address, denom, None)
+    }
+
+    fn query_balance_at(
+        &self,
+        address: &str,
+        denom: &str,
+        height: Option<u64>,
+    ) -> Result<cosmwasm_std::Coin> {
         use cosmrs::proto::cosmos::bank::v1beta1::{QueryBalanceRequest, QueryBalanceResponse};
         let path = "/cosmos.bank.v1beta1.Query/Balance";
         let msg = QueryBalanceRequest {
-            address: wallet.to_string(),
-            denom: "uscrt".to_owned(),
+            address: address.to_string(),
+            denom: denom.to_owned(),
         };
-        self.query_with_msg(path, msg)
+        self.query_with_msg_at(path, msg, height)
             .and_then(try_decode_response::<QueryBalanceResponse>)
             .and_then(|res| match res.balance {
-                Some(coin) => Ok(coin.amount.as_str().try_into()?),
-                None => Ok(cosmwasm_std::Uint128::zero()),
+                Some(coin) => try_coin_from_proto(coin),
+                None => Ok(cosmwasm_std::coin(0, denom)),
+            })
+    }
+
+    pub fn query_all_balances(&self, address: &str) -> Result<Vec<cosmwasm_std::Coin>> {
+        self.query_all_balances_at(address, None)
+    }
+
+    fn query_all_balances_at(
+        &self,
+        address: &str,
+        height: Option<u64>,
+    ) -> Result<Vec<cosmwasm_std::Coin>> {
+        use cosmrs::proto::cosmos::bank::v1beta1::{
+            QueryAllBalancesRequest, QueryAllBalancesResponse,
+        };
+        let path = "/cosmos.bank.v1beta1.Query/AllBalances";
+        self.query_all_pages(|pagination| {
+            let msg = QueryAllBalancesRequest {
+                address: address.to_string(),
+                pagination,
+            };
+            self.query_with_msg_at(path, msg, height)
+                .and_then(try_decode_response::<QueryAllBalancesResponse>)
+                .map(|res| (res.balances, res.pagination))
+        })?
+        .into_iter()
+        .map(try_coin_from_proto)
+        .collect()
+    }
+
+    pub fn query_total_supply(&self) -> Result<Vec<cosmwasm_std::Coin>> {
+        use cosmrs::proto::cosmos::bank::v1beta1::{
+            QueryTotalSupplyRequest, QueryTotalSupplyResponse,
+        };
+        let path = "/cosmos.bank.v1beta1.Query/TotalSupply";
+        self.query_all_pages(|pagination| {
+            let msg = QueryTotalSupplyRequest { pagination };
+            self.query_with_msg(path, msg)
+                .and_then(try_decode_response::<QueryTotalSupplyResponse>)
+                .map(|res| (res.supply, res.pagination))
+        })?
+        .into_iter()
+        .map(try_coin_from_proto)
+        .collect()
+    }
+
+    pub fn query_supply_of(&self, denom: &str) -> Result<cosmwasm_std::Coin> {
+        use cosmrs::proto::cosmos::bank::v1beta1::{QuerySupplyOfRequest, QuerySupplyOfResponse};
+        let path = "/cosmos.bank.v1beta1.Query/SupplyOf";
+        let msg = QuerySupplyOfRequest {
+            denom: denom.to_owned(),
+        };
+        self.query_with_msg(path, msg)
+            .and_then(try_decode_response::<QuerySupplyOfResponse>)
+            .and_then(|res| match res.amount {
+                Some(coin) => try_coin_from_proto(coin),
+                None => Ok(cosmwasm_std::coin(0, denom)),
             })
     }
 
@@ -35,10 +117,162 @@ impl super::Client {
             .map(|ci| CodeHash::from(ci.data_hash))
     }
 
+    pub fn contract_at(&self, address: &str) -> Result<Contract> {
+        use cosmrs::proto::cosmwasm::secret::compute::v1beta1::{
+            QueryContractInfoRequest, QueryContractInfoResponse,
+        };
+        let path = "/secret.compute.v1beta1.Query/ContractInfo";
+        let id: AccountId = address.parse()?;
+        let msg = QueryContractInfoRequest {
+            address: id.to_bytes(),
+        };
+        let code_id = self
+            .query_with_msg(path, msg)
+            .and_then(try_decode_response::<QueryContractInfoResponse>)
+            .and_then(|res| {
+                res.contract_info
+                    .ok_or_else(|| Error::ContractNotFound(address.into()))
+            })
+            .map(|ci| CodeId::from(ci.code_id))?;
+        let code_hash = self.query_code_hash_by_code_id(code_id)?;
+        Ok(Contract::new(id, code_hash))
+    }
+
+    pub fn contract_by_label(&self, label: &str) -> Result<Option<Contract>> {
+        let path = format!("custom/compute/label/{label}");
+        let res = self.query_path(&path)?;
+        if is_not_found(&res) {
+            return Ok(None);
+        }
+        let value = try_response_value(res)?;
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let id = AccountId::new(crate::consts::CHAIN_PREFIX, &value)?;
+        self.contract_at(id.as_ref()).map(Some)
+    }
+
+    /// The compute queries of this chain version take no `PageRequest`, so the listing is
+    /// fetched whole and only the codes of the requested page are decoded.
+    pub fn list_codes(&self, pagination: Pagination) -> Result<Page<CodeInfo>> {
+        use cosmrs::proto::cosmwasm::secret::compute::v1beta1::QueryCodesResponse;
+        let path = "/secret.compute.v1beta1.Query/Codes";
+        let code_infos = self
+            .query_path(path)
+            .and_then(try_decode_response::<QueryCodesResponse>)?
+            .code_infos;
+
+        pagination.apply(code_infos).try_map(|ci| {
+            Ok(CodeInfo {
+                code_id: CodeId::from(ci.code_id),
+                creator: try_human_address_from_bytes(&ci.creator)?,
+                code_hash: CodeHash::from(ci.data_hash),
+                source: ci.source,
+                builder: ci.builder,
+            })
+        })
+    }
+
+    /// Like [`Client::list_codes`], only the contracts of the requested page are decoded.
+    pub fn contracts_by_code(
+        &self,
+        code_id: CodeId,
+        pagination: Pagination,
+    ) -> Result<Page<ContractInfo>> {
+        use cosmrs::proto::cosmwasm::secret::compute::v1beta1::{
+            QueryContractsByCodeRequest, QueryContractsByCodeResponse,
+        };
+        let path = "/secret.compute.v1beta1.Query/ContractsByCode";
+        let msg = QueryContractsByCodeRequest {
+            code_id: code_id.into(),
+        };
+        let contract_infos = self
+            .query_with_msg(path, msg)
+            .and_then(try_decode_response::<QueryContractsByCodeResponse>)?
+            .contract_infos;
+
+        let page = pagination.apply(contract_infos);
+        if page.items.is_empty() {
+            return Ok(Page {
+                items: vec![],
+                total: page.total,
+            });
+        }
+
+        let code_hash = self.query_code_hash_by_code_id(code_id)?;
+
+        page.try_map(|ciwa| {
+            let id = AccountId::new(crate::consts::CHAIN_PREFIX, &ciwa.address)?;
+            let ci = ciwa
+                .contract_info
+                .ok_or_else(|| Error::ContractNotFound(id.as_ref().into()))?;
+            Ok(ContractInfo {
+                contract: Contract::new(id, code_hash.clone()),
+                code_id,
+                creator: try_human_address_from_bytes(&ci.creator)?,
+                label: ci.label,
+            })
+        })
+    }
+
     pub fn query_contract<M, R>(&self, msg: &M, contract: &Contract, from: &Account) -> Result<R>
     where
         M: serde::Serialize,
         R: serde::de::DeserializeOwned,
+    {
+        self.query_contract_at(msg, contract, from, None)
+    }
+
+    fn query_contract_at<M, R>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+        height: Option<u64>,
+    ) -> Result<R>
+    where
+        M: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        self.query_contract_raw_at(msg, contract, from, height)
+            .and_then(|buf| serde_json::from_slice(&buf).map_err(crate::Error::from))
+    }
+
+    /// Queries a contract without a typed response.
+    pub fn query_contract_json<M>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+    ) -> Result<serde_json::Value>
+    where
+        M: serde::Serialize,
+    {
+        self.query_contract(msg, contract, from)
+    }
+
+    /// Queries a contract, returning the decrypted response bytes as produced by the contract.
+    pub fn query_contract_raw<M>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+    ) -> Result<Vec<u8>>
+    where
+        M: serde::Serialize,
+    {
+        self.query_contract_raw_at(msg, contract, from, None)
+    }
+
+    fn query_contract_raw_at<M>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+        height: Option<u64>,
+    ) -> Result<Vec<u8>>
+    where
+        M: serde::Serialize,
     {
         use cosmrs::proto::cosmwasm::secret::compute::v1beta1::{
             QuerySmartContractStateRequest, QuerySmartContractStateResponse,
@@ -52,30 +286,69 @@ impl super::Client {
 
         let decrypter = self.decrypter(&nonce, from)?;
 
-        self.query_with_msg(path, msg)
+        self.query_with_msg_at(path, msg, height)
             .and_then(try_decode_response::<QuerySmartContractStateResponse>)
             .and_then(|res| decrypter.decrypt(&res.data).map_err(crate::Error::from))
             .and_then(|plt| String::from_utf8(plt).map_err(crate::Error::from))
             .and_then(|b46| base64::decode(b46).map_err(crate::Error::from))
-            .and_then(|buf| serde_json::from_slice(&buf).map_err(crate::Error::from))
     }
 
-    pub(crate) fn query_account_info(&self, account: &Account) -> Result<AccountInfo> {
-        use cosmrs::proto::cosmos::auth::v1beta1::{
-            BaseAccount, QueryAccountRequest, QueryAccountResponse,
-        };
+    pub fn query_account(&self, address: &str) -> Result<AccountInfo> {
+        self.query_account_at(address, None)
+    }
+
+    fn query_account_at(&self, address: &str, height: Option<u64>) -> Result<AccountInfo> {
+        use cosmrs::proto::cosmos::auth::v1beta1::{QueryAccountRequest, QueryAccountResponse};
         let path = "/cosmos.auth.v1beta1.Query/Account";
         let msg = QueryAccountRequest {
-            address: account.id().to_string(),
+            address: address.to_string(),
         };
-        self.query_with_msg(path, msg)
+        self.query_with_msg_at(path, msg, height)
             .and_then(try_decode_response::<QueryAccountResponse>)
             .and_then(|res| {
                 res.account
-                    .ok_or_else(|| Error::AccountNotFound(account.human_address()))
+                    .ok_or_else(|| Error::AccountNotFound(address.into()))
             })
-            .and_then(try_decode_any::<BaseAccount>)
-            .map(AccountInfo::from)
+            .and_then(try_decode_account)
+    }
+
+    pub fn tx_by_hash(&self, hash: &str) -> Result<TxInfo> {
+        let hash = hash
+            .parse()
+            .map_err(|_| Error::InvalidTxHash(hash.to_owned()))?;
+        let res = self.block_on(self.rpc.tx(hash, false))?;
+        TxInfo::try_from_rpc(res)
+    }
+
+    /// Searches committed transactions with a Tendermint event query,
+    /// e.g. `message.sender='secret1...'`, in ascending order of height.
+    pub fn search_txs(&self, query: &str) -> Result<Vec<TxInfo>> {
+        const PER_PAGE: u8 = 100;
+
+        let query: cosmrs::rpc::query::Query = query.parse()?;
+        let mut txs = vec![];
+
+        for page in 1.. {
+            let req = self.rpc.tx_search(
+                query.clone(),
+                false,
+                page,
+                PER_PAGE,
+                cosmrs::rpc::Order::Ascending,
+            );
+            let res = self.block_on(req)?;
+            let page_len = res.txs.len();
+
+            for tx in res.txs {
+                txs.push(TxInfo::try_from_rpc(tx)?);
+            }
+
+            if page_len < PER_PAGE as usize || txs.len() >= res.total_count as usize {
+                break;
+            }
+        }
+
+        Ok(txs)
     }
 
     pub(crate) fn query_tx_key(&self) -> Result<Vec<u8>> {
@@ -91,8 +364,61 @@ impl super::Client {
         self.query_path(&path).map(|res| res.code.is_ok())
     }
 
+    /// Queries any gRPC query service of the node, e.g. `/cosmos.mint.v1beta1.Query/Inflation`.
+    /// `req` can also be a `cosmrs::Any` whose value is the encoded request.
+    pub fn abci_query<Req, Res>(&self, path: &str, req: Req) -> Result<Res>
+    where
+        Req: Message + 'static,
+        Res: Message + Default,
+    {
+        // the node decodes the query data as the request itself, not as an `Any` envelope
+        let data = match (&req as &dyn std::any::Any).downcast_ref::<cosmrs::Any>() {
+            Some(any) => any.value.clone(),
+            None => req.encode_to_vec(),
+        };
+        self.query(path, data).and_then(try_decode_response::<Res>)
+    }
+
+    /// Like [`Client::abci_query`] but with raw request and response bytes.
+    pub fn abci_query_raw(&self, path: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+        self.query(path, data).and_then(try_response_value)
+    }
+
+    /// Follows the `next_key` of each page until the whole result set has been collected.
+    fn query_all_pages<T, F>(&self, mut query_page: F) -> Result<Vec<T>>
+    where
+        F: FnMut(Option<PageRequest>) -> Result<(Vec<T>, Option<PageResponse>)>,
+    {
+        let mut items = vec![];
+        let mut next_key = vec![];
+
+        loop {
+            let page = PageRequest {
+                key: next_key,
+                ..Default::default()
+            };
+
+            let (page_items, page_res) = query_page(Some(page))?;
+            items.extend(page_items);
+
+            match page_res {
+                Some(res) if !res.next_key.is_empty() => next_key = res.next_key,
+                _ => return Ok(items),
+            }
+        }
+    }
+
     fn query_with_msg(&self, path: &str, msg: impl Message) -> Result<QueryResponse> {
-        self.query(path, msg.encode_to_vec())
+        self.query_with_msg_at(path, msg, None)
+    }
+
+    fn query_with_msg_at(
+        &self,
+        path: &str,
+        msg: impl Message,
+        height: Option<u64>,
+    ) -> Result<QueryResponse> {
+        self.query_at(path, msg.encode_to_vec(), height)
     }
 
     fn query_path(&self, path: &str) -> Result<QueryResponse> {
@@ -100,19 +426,97 @@ impl super::Client {
     }
 
     fn query(&self, path: &str, data: Vec<u8>) -> Result<QueryResponse> {
+        self.query_at(path, data, None)
+    }
+
+    fn query_at(&self, path: &str, data: Vec<u8>, height: Option<u64>) -> Result<QueryResponse> {
         let path = path.parse().expect("abci_query path conversion failed");
-        let req = self.rpc.abci_query(Some(path), data, None, false);
+        let height = height.map(super::block::to_height).transpose()?;
+        let req = self.rpc.abci_query(Some(path), data, height, false);
         let res = self.block_on(req)?;
         Ok(res)
     }
 }
 
 fn try_decode_response<T: Message + Default>(response: QueryResponse) -> Result<T> {
+    try_response_value(response).and_then(|value| try_decode_bytes(&value))
+}
+
+fn try_response_value(response: QueryResponse) -> Result<Vec<u8>> {
     if response.code.is_err() {
         return Err(Error::AbciQuery(response.log.to_string()));
     }
 
-    try_decode_bytes(&response.value)
+    Ok(response.value)
+}
+
+/// Whether the query failed with the compute module's `ErrNotFound` or the SDK's `ErrNotFound`
+/// or `ErrUnknownAddress`, every other failure is a real error.
+fn is_not_found(response: &QueryResponse) -> bool {
+    const COMPUTE_ERR_NOT_FOUND: u32 = 8;
+    const SDK_ERR_UNKNOWN_ADDRESS: u32 = 9;
+    const SDK_ERR_NOT_FOUND: u32 = 38;
+
+    if response.code.is_ok() {
+        return false;
+    }
+
+    match (response.codespace.as_str(), response.code.value()) {
+        ("compute", COMPUTE_ERR_NOT_FOUND) => true,
+        ("sdk", SDK_ERR_UNKNOWN_ADDRESS | SDK_ERR_NOT_FOUND) => true,
+        _ => false,
+    }
+}
+
+fn try_decode_account(any: cosmrs::Any) -> Result<AccountInfo> {
+    use cosmrs::proto::cosmos::{
+        auth::v1beta1::{BaseAccount, ModuleAccount},
+        vesting::v1beta1::{
+            BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount,
+            PeriodicVestingAccount, PermanentLockedAccount,
+        },
+    };
+
+    fn vesting_base(bva: Option<BaseVestingAccount>) -> BaseAccount {
+        bva.and_then(|bva| bva.base_account).unwrap_or_default()
+    }
+
+    let info = match any.type_url.as_str() {
+        "/cosmos.auth.v1beta1.BaseAccount" => {
+            AccountInfo::new(try_decode_any::<BaseAccount>(any)?, AccountKind::Base)
+        }
+        "/cosmos.auth.v1beta1.ModuleAccount" => {
+            let ma = try_decode_any::<ModuleAccount>(any)?;
+            let kind = AccountKind::Module {
+                name: ma.name,
+                permissions: ma.permissions,
+            };
+            AccountInfo::new(ma.base_account.unwrap_or_default(), kind)
+        }
+        "/cosmos.vesting.v1beta1.ContinuousVestingAccount" => {
+            let va = try_decode_any::<ContinuousVestingAccount>(any)?;
+            let ba = vesting_base(va.base_vesting_account);
+            AccountInfo::new(ba, AccountKind::ContinuousVesting)
+        }
+        "/cosmos.vesting.v1beta1.DelayedVestingAccount" => {
+            let va = try_decode_any::<DelayedVestingAccount>(any)?;
+            let ba = vesting_base(va.base_vesting_account);
+            AccountInfo::new(ba, AccountKind::DelayedVesting)
+        }
+        "/cosmos.vesting.v1beta1.PeriodicVestingAccount" => {
+            let va = try_decode_any::<PeriodicVestingAccount>(any)?;
+            let ba = vesting_base(va.base_vesting_account);
+            AccountInfo::new(ba, AccountKind::PeriodicVesting)
+        }
+        "/cosmos.vesting.v1beta1.PermanentLockedAccount" => {
+            let va = try_decode_any::<PermanentLockedAccount>(any)?;
+            let ba = vesting_base(va.base_vesting_account);
+            AccountInfo::new(ba, AccountKind::PermanentLocked)
+        }
+        _ => return Err(Error::UnsupportedAccountType(any.type_url)),
+    };
+
+    Ok(info)
 }
 
 fn try_decode_any<T: Message + Default>(any: cosmrs::Any) -> Result<T> {
@@ -123,3 +527,22 @@ fn try_decode_bytes<T: Message + Default>(bytes: &[u8]) -> Result<T> {
     let t = T::decode(bytes)?;
     Ok(t)
 }
+
+fn try_coin_from_proto(
+    coin: cosmrs::proto::cosmos::base::v1beta1::Coin,
+) -> Result<cosmwasm_std::Coin> {
+    Ok(cosmwasm_std::Coin {
+        amount: coin.amount.as_str().try_into()?,
+        denom: coin.denom,
+    })
+}
+
+fn try_human_address_from_bytes(bytes: &[u8]) -> Result<cosmwasm_std::HumanAddr> {
+    let id = AccountId::new(crate::consts::CHAIN_PREFIX, bytes)?;
+    Ok(id.as_ref().into())
+}
+
+pub(crate) fn system_time(seconds: i64, nanos: i32) -> std::time::SystemTime {
+    let since_epoch = std::time::Duration::new(seconds.max(0) as u64, nanos.max(0) as u32);
+    std::time::SystemTime::UNIX_EPOCH + since_epoch
+}
@@ -0,0 +1,74 @@
+use std::time::SystemTime;
+
+use cosmrs::proto::cosmos::gov::v1beta1 as gov;
+
+use crate::{
+    client::types::{Proposal, ProposalStatus, TallyResult},
+    Error, Result,
+};
+
+use super::{system_time, try_coin_from_proto, try_decode_response};
+
+impl crate::Client {
+    pub fn query_proposal(&self, proposal_id: u64) -> Result<Proposal> {
+        use gov::{QueryProposalRequest, QueryProposalResponse};
+        let path = "/cosmos.gov.v1beta1.Query/Proposal";
+        let msg = QueryProposalRequest { proposal_id };
+        self.query_with_msg(path, msg)
+            .and_then(try_decode_response::<QueryProposalResponse>)
+            .and_then(|res| res.proposal.ok_or(Error::ProposalNotFound(proposal_id)))
+            .and_then(try_proposal_from_proto)
+    }
+
+    /// Blocks until the voting period of the proposal has ended and returns the tallied proposal.
+    pub fn wait_for_voting_period_end(&self, proposal_id: u64) -> Result<Proposal> {
+        loop {
+            let proposal = self.query_proposal(proposal_id)?;
+            match proposal.status {
+                ProposalStatus::VotingPeriod => {
+                    // the proposal is tallied in the first block after the voting end time
+                    self.wait_until_time(proposal.voting_end_time)?;
+                    self.wait_blocks(1)?;
+                }
+                ProposalStatus::DepositPeriod | ProposalStatus::Unspecified => {
+                    return Err(Error::ProposalNotInVotingPeriod(proposal_id))
+                }
+                _ => return Ok(proposal),
+            }
+        }
+    }
+}
+
+fn try_proposal_from_proto(p: gov::Proposal) -> Result<Proposal> {
+    let final_tally = match p.final_tally_result {
+        Some(tally) => TallyResult {
+            yes: tally.yes.as_str().try_into()?,
+            abstain: tally.abstain.as_str().try_into()?,
+            no: tally.no.as_str().try_into()?,
+            no_with_veto: tally.no_with_veto.as_str().try_into()?,
+        },
+        None => TallyResult::default(),
+    };
+    Ok(Proposal {
+        id: p.proposal_id,
+        status: p.status.into(),
+        final_tally,
+        total_deposit: p
+            .total_deposit
+            .into_iter()
+            .map(try_coin_from_proto)
+            .collect::<Result<_>>()?,
+        submit_time: p.submit_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+            system_time(ts.seconds, ts.nanos)
+        }),
+        deposit_end_time: p.deposit_end_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+            system_time(ts.seconds, ts.nanos)
+        }),
+        voting_start_time: p.voting_start_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+            system_time(ts.seconds, ts.nanos)
+        }),
+        voting_end_time: p.voting_end_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+            system_time(ts.seconds, ts.nanos)
+        }),
+    })
+}
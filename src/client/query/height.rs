@@ -0,0 +1,66 @@
+use crate::{client::types::AtHeight, Account, AccountInfo, Contract, Result};
+
+impl crate::Client {
+    /// Queries the state as of block `height`.
+    /// Fails with an ABCI query error if the node has already pruned that height.
+    pub fn at_height(&self, height: u64) -> AtHeight<'_> {
+        AtHeight {
+            client: self,
+            height,
+        }
+    }
+}
+
+impl<'a> AtHeight<'a> {
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn query_balance(&self, address: &str, denom: &str) -> Result<cosmwasm_std::Coin> {
+        self.client
+            .query_balance_at(address, denom, Some(self.height))
+    }
+
+    pub fn query_all_balances(&self, address: &str) -> Result<Vec<cosmwasm_std::Coin>> {
+        self.client
+            .query_all_balances_at(address, Some(self.height))
+    }
+
+    pub fn query_account(&self, address: &str) -> Result<AccountInfo> {
+        self.client.query_account_at(address, Some(self.height))
+    }
+
+    pub fn query_contract<M, R>(&self, msg: &M, contract: &Contract, from: &Account) -> Result<R>
+    where
+        M: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        self.client
+            .query_contract_at(msg, contract, from, Some(self.height))
+    }
+
+    pub fn query_contract_json<M>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+    ) -> Result<serde_json::Value>
+    where
+        M: serde::Serialize,
+    {
+        self.query_contract(msg, contract, from)
+    }
+
+    pub fn query_contract_raw<M>(
+        &self,
+        msg: &M,
+        contract: &Contract,
+        from: &Account,
+    ) -> Result<Vec<u8>>
+    where
+        M: serde::Serialize,
+    {
+        self.client
+            .query_contract_raw_at(msg, contract, from, Some(self.height))
+    }
+}
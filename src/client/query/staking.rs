@@ -0,0 +1,265 @@
+use std::time::SystemTime;
+
+use cosmrs::proto::cosmos::{
+    base::v1beta1::DecCoin as ProtoDecCoin, distribution::v1beta1 as distribution,
+    staking::v1beta1 as staking,
+};
+use cosmwasm_std::Decimal;
+
+use crate::{
+    client::types::{
+        DecCoin, Delegation, DelegatorRewards, Redelegation, RedelegationEntry,
+        UnbondingDelegation, UnbondingEntry, Validator, ValidatorRewards,
+    },
+    consts, Error, Result,
+};
+
+use super::{system_time, try_coin_from_proto, try_decode_response};
+
+/// `sdk.Dec` values are encoded in protobuf as integers scaled by 10^18.
+const DEC_PRECISION: usize = 18;
+
+impl crate::Client {
+    /// Lists validators in every bond status.
+    pub fn query_validators(&self) -> Result<Vec<Validator>> {
+        use staking::{QueryValidatorsRequest, QueryValidatorsResponse};
+        let path = "/cosmos.staking.v1beta1.Query/Validators";
+        self.query_all_pages(|pagination| {
+            let msg = QueryValidatorsRequest {
+                status: String::new(),
+                pagination,
+            };
+            self.query_with_msg(path, msg)
+                .and_then(try_decode_response::<QueryValidatorsResponse>)
+                .map(|res| (res.validators, res.pagination))
+        })?
+        .into_iter()
+        .map(try_validator_from_proto)
+        .collect()
+    }
+
+    pub fn query_validator(&self, validator: &str) -> Result<Validator> {
+        use staking::{QueryValidatorRequest, QueryValidatorResponse};
+        let path = "/cosmos.staking.v1beta1.Query/Validator";
+        let msg = QueryValidatorRequest {
+            validator_addr: validator.to_owned(),
+        };
+        self.query_with_msg(path, msg)
+            .and_then(try_decode_response::<QueryValidatorResponse>)
+            .and_then(|res| {
+                res.validator
+                    .ok_or_else(|| Error::ValidatorNotFound(validator.to_owned()))
+            })
+            .and_then(try_validator_from_proto)
+    }
+
+    pub fn query_delegations(&self, delegator: &str) -> Result<Vec<Delegation>> {
+        use staking::{QueryDelegatorDelegationsRequest, QueryDelegatorDelegationsResponse};
+        let path = "/cosmos.staking.v1beta1.Query/DelegatorDelegations";
+        self.query_all_pages(|pagination| {
+            let msg = QueryDelegatorDelegationsRequest {
+                delegator_addr: delegator.to_owned(),
+                pagination,
+            };
+            self.query_with_msg(path, msg)
+                .and_then(try_decode_response::<QueryDelegatorDelegationsResponse>)
+                .map(|res| (res.delegation_responses, res.pagination))
+        })?
+        .into_iter()
+        .map(|res| {
+            let delegation = res.delegation.unwrap_or_default();
+            let balance = match res.balance {
+                Some(coin) => try_coin_from_proto(coin)?,
+                None => cosmwasm_std::coin(0, consts::COIN_DENOM),
+            };
+            Ok(Delegation {
+                delegator: delegation.delegator_address.into(),
+                validator: delegation.validator_address,
+                shares: try_decimal_from_proto(&delegation.shares)?,
+                balance,
+            })
+        })
+        .collect()
+    }
+
+    pub fn query_unbonding_delegations(&self, delegator: &str) -> Result<Vec<UnbondingDelegation>> {
+        use staking::{
+            QueryDelegatorUnbondingDelegationsRequest, QueryDelegatorUnbondingDelegationsResponse,
+        };
+        let path = "/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations";
+        self.query_all_pages(|pagination| {
+            let msg = QueryDelegatorUnbondingDelegationsRequest {
+                delegator_addr: delegator.to_owned(),
+                pagination,
+            };
+            self.query_with_msg(path, msg)
+                .and_then(try_decode_response::<QueryDelegatorUnbondingDelegationsResponse>)
+                .map(|res| (res.unbonding_responses, res.pagination))
+        })?
+        .into_iter()
+        .map(|ubd| {
+            let entries = ubd
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    Ok(UnbondingEntry {
+                        creation_height: entry.creation_height as u64,
+                        completion_time: entry
+                            .completion_time
+                            .map_or(SystemTime::UNIX_EPOCH, |ts| {
+                                system_time(ts.seconds, ts.nanos)
+                            }),
+                        initial_balance: entry.initial_balance.as_str().try_into()?,
+                        balance: entry.balance.as_str().try_into()?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Ok(UnbondingDelegation {
+                delegator: ubd.delegator_address.into(),
+                validator: ubd.validator_address,
+                entries,
+            })
+        })
+        .collect()
+    }
+
+    pub fn query_redelegations(&self, delegator: &str) -> Result<Vec<Redelegation>> {
+        use staking::{QueryRedelegationsRequest, QueryRedelegationsResponse};
+        let path = "/cosmos.staking.v1beta1.Query/Redelegations";
+        self.query_all_pages(|pagination| {
+            let msg = QueryRedelegationsRequest {
+                delegator_addr: delegator.to_owned(),
+                src_validator_addr: String::new(),
+                dst_validator_addr: String::new(),
+                pagination,
+            };
+            self.query_with_msg(path, msg)
+                .and_then(try_decode_response::<QueryRedelegationsResponse>)
+                .map(|res| (res.redelegation_responses, res.pagination))
+        })?
+        .into_iter()
+        .map(|res| {
+            let red = res.redelegation.unwrap_or_default();
+            let entries = res
+                .entries
+                .into_iter()
+                .map(|entry_res| {
+                    let entry = entry_res.redelegation_entry.unwrap_or_default();
+                    Ok(RedelegationEntry {
+                        creation_height: entry.creation_height as u64,
+                        completion_time: entry
+                            .completion_time
+                            .map_or(SystemTime::UNIX_EPOCH, |ts| {
+                                system_time(ts.seconds, ts.nanos)
+                            }),
+                        initial_balance: entry.initial_balance.as_str().try_into()?,
+                        shares_dst: try_decimal_from_proto(&entry.shares_dst)?,
+                        balance: entry_res.balance.as_str().try_into()?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Ok(Redelegation {
+                delegator: red.delegator_address.into(),
+                src_validator: red.validator_src_address,
+                dst_validator: red.validator_dst_address,
+                entries,
+            })
+        })
+        .collect()
+    }
+
+    pub fn query_delegator_rewards(&self, delegator: &str) -> Result<DelegatorRewards> {
+        use distribution::{
+            QueryDelegationTotalRewardsRequest, QueryDelegationTotalRewardsResponse,
+        };
+        let path = "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards";
+        let msg = QueryDelegationTotalRewardsRequest {
+            delegator_address: delegator.to_owned(),
+        };
+        let res = self
+            .query_with_msg(path, msg)
+            .and_then(try_decode_response::<QueryDelegationTotalRewardsResponse>)?;
+        let rewards = res
+            .rewards
+            .into_iter()
+            .map(|r| {
+                Ok(ValidatorRewards {
+                    validator: r.validator_address,
+                    reward: try_dec_coins_from_proto(r.reward)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(DelegatorRewards {
+            rewards,
+            total: try_dec_coins_from_proto(res.total)?,
+        })
+    }
+
+    pub fn query_community_pool(&self) -> Result<Vec<DecCoin>> {
+        use distribution::{QueryCommunityPoolRequest, QueryCommunityPoolResponse};
+        let path = "/cosmos.distribution.v1beta1.Query/CommunityPool";
+        self.query_with_msg(path, QueryCommunityPoolRequest {})
+            .and_then(try_decode_response::<QueryCommunityPoolResponse>)
+            .and_then(|res| try_dec_coins_from_proto(res.pool))
+    }
+}
+
+fn try_validator_from_proto(v: staking::Validator) -> Result<Validator> {
+    let commission_rate = v
+        .commission
+        .and_then(|c| c.commission_rates)
+        .map(|rates| try_decimal_from_proto(&rates.rate))
+        .transpose()?
+        .unwrap_or_else(Decimal::zero);
+    Ok(Validator {
+        operator_address: v.operator_address,
+        moniker: v.description.map(|d| d.moniker).unwrap_or_default(),
+        jailed: v.jailed,
+        status: v.status.into(),
+        tokens: v.tokens.as_str().try_into()?,
+        delegator_shares: try_decimal_from_proto(&v.delegator_shares)?,
+        commission_rate,
+    })
+}
+
+fn try_dec_coins_from_proto(coins: Vec<ProtoDecCoin>) -> Result<Vec<DecCoin>> {
+    coins
+        .into_iter()
+        .map(|coin| {
+            Ok(DecCoin {
+                amount: try_decimal_from_proto(&coin.amount)?,
+                denom: coin.denom,
+            })
+        })
+        .collect()
+}
+
+fn try_decimal_from_proto(atomics: &str) -> Result<Decimal> {
+    if atomics.is_empty() {
+        return Ok(Decimal::zero());
+    }
+    let padded = format!("{:0>width$}", atomics, width = DEC_PRECISION + 1);
+    let (int, frac) = padded.split_at(padded.len() - DEC_PRECISION);
+    let decimal = format!("{int}.{frac}").parse()?;
+    Ok(decimal)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decimal_from_proto_atomics() {
+        let dec = |s: &str| s.parse::<Decimal>().unwrap();
+        assert_eq!(try_decimal_from_proto("").unwrap(), Decimal::zero());
+        assert_eq!(try_decimal_from_proto("0").unwrap(), Decimal::zero());
+        assert_eq!(
+            try_decimal_from_proto("50000000000000000").unwrap(),
+            dec("0.05")
+        );
+        assert_eq!(
+            try_decimal_from_proto("1234500000000000000000").unwrap(),
+            dec("1234.5")
+        );
+    }
+}
@@ -1,29 +1,446 @@
+use std::time::{Duration, Instant};
+
 use cosmrs::{
-    rpc::endpoint::broadcast::tx_commit::Response as BroadcastResponse,
-    tx::{Body, Fee, Msg, MsgProto, SignDoc, SignerInfo},
+    proto::cosmos::{
+        base::abci::v1beta1::{MsgData, TxMsgData},
+        tx::v1beta1::{SimulateRequest, SimulateResponse},
+    },
+    rpc::{endpoint::broadcast::tx_commit::Response as BroadcastResponse, Client as RpcClient},
+    tendermint::abci::{transaction::Hash, DeliverTx},
+    tx::{AuthInfo, Body, Fee, Raw, SignDoc, SignerInfo},
+    AccountId,
 };
 use prost::Message;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    account::Account,
+    client::types::{Event, GasPrice},
+    crypto::Decrypter,
+    Error, Result, TxResponse,
+};
+
+use builder::{BroadcastMode, PreparedTx, SignMode};
 
-use crate::{account::Account, client::types::Event, crypto::Decrypter, Error, Result, TxResponse};
+// legacy amino json sign docs
+mod amino;
 
 pub mod builder {
     use std::{
         marker::PhantomData,
         path::{Path, PathBuf},
+        time::Duration,
     };
 
-    use cosmrs::{tx::Fee, Coin};
+    use cosmrs::{
+        proto::cosmos::{base::abci::v1beta1::MsgData, tx::v1beta1 as proto},
+        tx::{Body, Msg},
+        AccountId, Coin,
+    };
+    use prost::Message;
 
     use crate::{
-        client::types::ContractInit, Account, CodeId, Contract, Error, Result, TxResponse,
+        client::types::ContractInit, crypto::Decrypter, Account, CodeId, Contract, Error,
+        FeeProfile, GasPrice, Multisig, Result, TxResponse,
     };
 
+    use super::{BodyOptions, FeeOptions, Gas, Sequence, Timeout, TxFee};
+
+    // bank send and multi-send txs
+    mod bank;
+    // multi-message txs
+    mod batch;
+    // governance proposal, deposit and vote txs
+    mod gov;
+    // delegation and reward withdrawal txs
+    mod staking;
+
+    pub use bank::{BankSend, MultiSend};
+    pub use batch::{Batch, BatchMsg, Batchable, MsgResponse};
+    pub use gov::{Deposit, ParamChange, ProposalContent, SubmitProposal, Vote, VoteOption};
+    pub use staking::{Delegate, Redelegate, Undelegate, WithdrawRewards};
+
     pub type InitTx<'a> = Tx<'a, Unspecified, Unspecified>;
 
     pub trait Broadcast {
         type Response;
 
-        fn broadcast(self) -> Result<TxResponse<Self::Response>>;
+        /// Builds and encrypts the messages of the tx.
+        fn prepare(self) -> Result<PreparedTx<Self::Response>>;
+    }
+
+    /// How long the node is waited on before a tx is returned.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BroadcastMode {
+        /// Returns after the tx passed `CheckTx`.
+        Sync,
+        /// Returns as soon as the node received the tx, `CheckTx` errors are not reported: a
+        /// rejected tx is never included and waiting for it ends in [`Error::TxTimeout`].
+        /// [`Tx::broadcast`] waits for `CheckTx` in this mode too.
+        Async,
+        /// Returns after the tx is included in a block.
+        Commit,
+    }
+
+    /// How the signers sign a tx.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SignMode {
+        /// `SIGN_MODE_DIRECT`, signs the protobuf encoded tx.
+        Direct,
+        /// `SIGN_MODE_LEGACY_AMINO_JSON`, signs the canonical Amino JSON of the tx as hardware
+        /// wallets do.
+        LegacyAminoJson,
+    }
+
+    /// The sender of the messages of a tx.
+    #[derive(Clone)]
+    pub enum Signer {
+        Account(Account),
+        /// Signed with [`Tx::unsigned`] and the partial signatures of its members.
+        Multisig(Multisig),
+    }
+
+    impl Signer {
+        pub(crate) fn id(&self) -> AccountId {
+            match self {
+                Signer::Account(account) => account.id(),
+                Signer::Multisig(multisig) => multisig.id(),
+            }
+        }
+
+        /// The account contract messages and their responses are encrypted with.
+        pub(crate) fn encryption_account(&self) -> &Account {
+            match self {
+                Signer::Account(account) => account,
+                Signer::Multisig(multisig) => multisig.encryption_account(),
+            }
+        }
+    }
+
+    impl From<Account> for Signer {
+        fn from(account: Account) -> Self {
+            Signer::Account(account)
+        }
+    }
+
+    impl From<Multisig> for Signer {
+        fn from(multisig: Multisig) -> Self {
+            Signer::Multisig(multisig)
+        }
+    }
+
+    /// The messages of a tx, ready to be signed, and how to read its typed response.
+    pub struct PreparedTx<R> {
+        pub(super) msgs: Vec<cosmrs::Any>,
+        pub(super) from: Signer,
+        pub(super) fee: TxFee,
+        pub(super) body: BodyOptions,
+        pub(super) sign_mode: SignMode,
+        pub(super) decrypters: Vec<Decrypter>,
+        pub(super) response: Box<dyn FnOnce(TxResponse<Vec<MsgData>>) -> Result<TxResponse<R>>>,
+    }
+
+    impl<R> PreparedTx<R> {
+        fn new(
+            msgs: Vec<cosmrs::Any>,
+            from: impl Into<Signer>,
+            fee: TxFee,
+            decrypters: Vec<Decrypter>,
+            response: impl FnOnce(TxResponse<Vec<MsgData>>) -> Result<TxResponse<R>> + 'static,
+        ) -> Self {
+            PreparedTx {
+                msgs,
+                from: from.into(),
+                fee,
+                body: BodyOptions::default(),
+                sign_mode: SignMode::Direct,
+                decrypters,
+                response: Box::new(response),
+            }
+        }
+
+        /// A tx with a single message whose response data is parsed with `parse`.
+        fn single(
+            msg: cosmrs::Any,
+            from: impl Into<Signer>,
+            fee: TxFee,
+            decrypters: Vec<Decrypter>,
+            parse: impl FnOnce(Vec<u8>) -> Result<R> + 'static,
+        ) -> Self {
+            let msg_type = msg.type_url.clone();
+            PreparedTx::new(vec![msg], from, fee, decrypters, move |tx| {
+                tx.find_msg_data(&msg_type).try_map(parse)
+            })
+        }
+    }
+
+    /// A tx that was accepted by the node but may not be included in a block yet.
+    pub struct PendingTx<'a, R> {
+        pub(super) client: &'a crate::Client,
+        pub(super) hash: String,
+        pub(super) decrypters: Vec<Decrypter>,
+        pub(super) response: Box<dyn FnOnce(TxResponse<Vec<MsgData>>) -> Result<TxResponse<R>>>,
+    }
+
+    impl<'a, R> PendingTx<'a, R> {
+        pub fn hash(&self) -> &str {
+            &self.hash
+        }
+
+        /// Polls for the tx until it is included in a block or `timeout` elapses. A tx sent with
+        /// [`Tx::broadcast_async`] that failed `CheckTx` times out.
+        pub fn wait(self, timeout: Duration) -> Result<TxResponse<R>> {
+            let tx = self
+                .client
+                .wait_for_tx_raw(&self.hash, timeout)?
+                .into_result_with_decrypt(&self.decrypters)?;
+            (self.response)(tx)
+        }
+    }
+
+    /// A signed tx that can be stored and broadcast later with
+    /// [`Client::broadcast_raw`](crate::Client::broadcast_raw).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SignedTx {
+        bytes: Vec<u8>,
+        hash: String,
+    }
+
+    impl SignedTx {
+        /// Reads the protobuf encoded `TxRaw` of a signed tx, which must hold a body with
+        /// messages, the auth info and signatures.
+        pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+            let invalid = |reason: &str| Error::InvalidSignedTx(reason.to_owned());
+
+            let tx_raw = proto::TxRaw::decode(bytes.as_slice())
+                .map_err(|err| invalid(&format!("not a TxRaw: {err}")))?;
+            let body = proto::TxBody::decode(tx_raw.body_bytes.as_slice())
+                .map_err(|err| invalid(&format!("invalid body: {err}")))?;
+            proto::AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+                .map_err(|err| invalid(&format!("invalid auth info: {err}")))?;
+            if body.messages.is_empty() {
+                return Err(invalid("no messages"));
+            }
+            if tx_raw.signatures.is_empty() {
+                return Err(invalid("no signatures"));
+            }
+
+            Ok(SignedTx::new(bytes))
+        }
+
+        pub fn from_base64(b64: &str) -> Result<Self> {
+            SignedTx::from_bytes(base64::decode(b64)?)
+        }
+
+        /// Bytes the client encoded itself.
+        pub(super) fn new(bytes: Vec<u8>) -> Self {
+            let hash = super::tx_hash(&bytes);
+            SignedTx { bytes, hash }
+        }
+
+        /// Reads the JSON written by [`SignedTx::to_json`] and checks its hash.
+        pub fn from_json(json: &str) -> Result<Self> {
+            let value: serde_json::Value = serde_json::from_str(json)?;
+            let tx = value["tx"]
+                .as_str()
+                .ok_or_else(|| Error::InvalidSignedTx("missing tx".to_owned()))?;
+            let signed = SignedTx::from_base64(tx)?;
+            match value["hash"].as_str() {
+                Some(hash) if hash.eq_ignore_ascii_case(&signed.hash) => Ok(signed),
+                _ => Err(Error::InvalidSignedTx(format!(
+                    "hash does not match tx {}",
+                    signed.hash
+                ))),
+            }
+        }
+
+        /// The hash the tx will be committed under.
+        pub fn hash(&self) -> &str {
+            &self.hash
+        }
+
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.bytes
+        }
+
+        pub fn to_base64(&self) -> String {
+            base64::encode(&self.bytes)
+        }
+
+        /// `{"hash": "<hex>", "tx": "<base64>"}`
+        pub fn to_json(&self) -> String {
+            serde_json::json!({
+                "hash": self.hash,
+                "tx": self.to_base64(),
+            })
+            .to_string()
+        }
+    }
+
+    /// A multisig tx waiting for the signatures of its members, see [`Tx::unsigned`]. It can be
+    /// passed to each member with [`UnsignedTx::to_json`] to be signed on their own machine.
+    pub struct UnsignedTx {
+        pub(super) body: Body,
+        pub(super) fee: proto::Fee,
+        pub(super) chain_id: String,
+        pub(super) sequence: Sequence,
+        pub(super) multisig: Multisig,
+    }
+
+    impl UnsignedTx {
+        /// Signs the tx as `member` in legacy Amino JSON mode, which needs no client.
+        pub fn sign_partial(&self, member: &Account) -> Result<PartialSignature> {
+            let index = self.multisig.member_index(member).ok_or_else(|| {
+                Error::Multisig(format!("{} is not a member", member.human_address()))
+            })?;
+            let sign_doc_bytes = super::amino::sign_doc_bytes(
+                &self.chain_id,
+                self.sequence.account_number,
+                self.sequence.sequence,
+                &self.fee,
+                &self.body,
+            )?;
+            let signature = member.signing_key().sign(&sign_doc_bytes)?;
+            Ok(PartialSignature {
+                member: index,
+                signature: signature.as_ref().to_vec(),
+            })
+        }
+
+        /// `{"chain_id": "<id>", "account_number": <n>, "sequence": <n>, "body": "<base64>",
+        /// "fee": "<base64>", "multisig": "<base64>"}` with the protobuf encoded body, fee and
+        /// `LegacyAminoPubKey` of the multisig.
+        pub fn to_json(&self) -> Result<String> {
+            Ok(serde_json::json!({
+                "chain_id": self.chain_id,
+                "account_number": self.sequence.account_number,
+                "sequence": self.sequence.sequence,
+                "body": base64::encode(self.body.clone().into_bytes()?),
+                "fee": base64::encode(self.fee.encode_to_vec()),
+                "multisig": base64::encode(self.multisig.public_key_any()?.value),
+            })
+            .to_string())
+        }
+
+        /// Reads the JSON written by [`UnsignedTx::to_json`].
+        pub fn from_json(json: &str) -> Result<Self> {
+            let value: serde_json::Value = serde_json::from_str(json)?;
+            let missing = |field: &str| Error::Multisig(format!("unsigned tx without {field}"));
+            let str_field = |field: &str| value[field].as_str().ok_or_else(|| missing(field));
+            let u64_field = |field: &str| value[field].as_u64().ok_or_else(|| missing(field));
+            let bytes_field = |field: &str| Ok::<_, Error>(base64::decode(str_field(field)?)?);
+
+            let body = proto::TxBody::decode(bytes_field("body")?.as_slice())?;
+            let multisig = Multisig::from_public_key_any(cosmrs::Any {
+                type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_owned(),
+                value: bytes_field("multisig")?,
+            })?;
+
+            Ok(UnsignedTx {
+                body: Body::try_from(body)?,
+                fee: proto::Fee::decode(bytes_field("fee")?.as_slice())?,
+                chain_id: str_field("chain_id")?.to_owned(),
+                sequence: Sequence {
+                    account_number: u64_field("account_number")?,
+                    sequence: u64_field("sequence")?,
+                },
+                multisig,
+            })
+        }
+
+        /// Combines the partial signatures of at least `threshold` members into a tx that can be
+        /// broadcast with [`Client::broadcast_raw`](crate::Client::broadcast_raw).
+        pub fn aggregate(&self, signatures: &[PartialSignature]) -> Result<SignedTx> {
+            use cosmrs::proto::cosmos::crypto::multisig::v1beta1::MultiSignature;
+
+            let members = self.multisig.members();
+            if let Some(sig) = signatures.iter().find(|sig| sig.member >= members) {
+                return Err(Error::Multisig(format!(
+                    "member {} of a multisig with {} members",
+                    sig.member, members
+                )));
+            }
+
+            let mut signatures = signatures.to_vec();
+            signatures.sort_by_key(|sig| sig.member);
+            signatures.dedup_by_key(|sig| sig.member);
+
+            let threshold = self.multisig.threshold() as usize;
+            if signatures.len() < threshold {
+                return Err(Error::Multisig(format!(
+                    "{} of {} required signatures",
+                    signatures.len(),
+                    threshold
+                )));
+            }
+
+            let signed: Vec<_> = signatures.iter().map(|sig| sig.member).collect();
+            let signer_info = proto::SignerInfo {
+                public_key: Some(self.multisig.public_key_any()?),
+                mode_info: Some(super::amino::multisig_mode_info(members, &signed)),
+                sequence: self.sequence.sequence,
+            };
+            let auth_info = proto::AuthInfo {
+                signer_infos: vec![signer_info],
+                fee: Some(self.fee.clone()),
+            };
+            let signature = MultiSignature {
+                signatures: signatures.into_iter().map(|sig| sig.signature).collect(),
+            };
+
+            let tx_raw = proto::TxRaw {
+                body_bytes: self.body.clone().into_bytes()?,
+                auth_info_bytes: auth_info.encode_to_vec(),
+                signatures: vec![signature.encode_to_vec()],
+            };
+            Ok(SignedTx::new(tx_raw.encode_to_vec()))
+        }
+    }
+
+    /// The signature of one multisig member, see [`UnsignedTx::sign_partial`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PartialSignature {
+        member: usize,
+        signature: Vec<u8>,
+    }
+
+    impl PartialSignature {
+        /// A signature made elsewhere by the member at position `member`.
+        pub fn new(member: usize, signature: Vec<u8>) -> Self {
+            PartialSignature { member, signature }
+        }
+
+        /// The position of the signer among the multisig members.
+        pub fn member(&self) -> usize {
+            self.member
+        }
+
+        pub fn signature(&self) -> &[u8] {
+            &self.signature
+        }
+
+        /// `{"member": <n>, "signature": "<base64>"}`
+        pub fn to_json(&self) -> String {
+            serde_json::json!({
+                "member": self.member,
+                "signature": base64::encode(&self.signature),
+            })
+            .to_string()
+        }
+
+        /// Reads the JSON written by [`PartialSignature::to_json`].
+        pub fn from_json(json: &str) -> Result<Self> {
+            let value: serde_json::Value = serde_json::from_str(json)?;
+            let member = value["member"]
+                .as_u64()
+                .ok_or_else(|| Error::Multisig("partial signature without member".to_owned()))?;
+            let signature = value["signature"]
+                .as_str()
+                .ok_or_else(|| Error::Multisig("partial signature without signature".to_owned()))?;
+            let member = usize::try_from(member)
+                .map_err(|_| Error::Multisig(format!("member {member} out of range")))?;
+            Ok(PartialSignature::new(member, base64::decode(signature)?))
+        }
     }
 
     pub struct Unspecified;
@@ -63,45 +480,203 @@ pub mod builder {
         client: &'a crate::Client,
         kind: Kind,
         from: From,
-        fee: Option<Fee>,
+        fee: FeeOptions,
+        body: BodyOptions,
+        sign_mode: SignMode,
     }
 
     impl<'a, Kind, From> Tx<'a, Kind, From> {
+        /// A fixed fee `amount` in the fee denom and a gas limit, regardless of the gas price.
         pub fn gas_fee(mut self, amount: u64, gas: u64) -> Self {
-            let fee = super::gas::fee(amount, gas);
-            self.fee = Some(fee);
+            self.fee.gas = Some(Gas::Fixed { amount, gas });
             self
         }
 
-        pub fn broadcast(self) -> Result<TxResponse<<Self as Broadcast>::Response>>
+        /// A gas limit, paid for at the gas price.
+        pub fn gas_limit(mut self, gas: u64) -> Self {
+            self.fee.gas = Some(Gas::Limit(gas));
+            self
+        }
+
+        /// Sets the gas limit to the gas used when simulating the tx right before it is
+        /// broadcast, times `multiplier` (e.g. `1.3`) as a safety margin. Broadcasting fails with
+        /// [`Error::InvalidGasMultiplier`] unless `multiplier` is finite and at least `1.0`.
+        pub fn gas_auto(mut self, multiplier: f64) -> Self {
+            self.fee.gas = Some(Gas::Auto(multiplier));
+            self
+        }
+
+        /// Overrides the client gas price for this tx. Its denom is the denom the fee is paid in.
+        pub fn gas_price(mut self, gas_price: GasPrice) -> Self {
+            self.fee.gas_price = Some(gas_price);
+            self
+        }
+
+        pub fn fee_profile(self, profile: FeeProfile) -> Self {
+            self.gas_price(profile.gas_price())
+        }
+
+        /// Deducts the fee from a fee grant `granter` gave to the signer.
+        pub fn fee_granter(mut self, granter: &Account) -> Self {
+            self.fee.granter = Some(granter.clone());
+            self
+        }
+
+        /// Has `payer` pay the fee. The payer co-signs the tx.
+        pub fn fee_payer(mut self, payer: &Account) -> Self {
+            self.fee.payer = Some(payer.clone());
+            self
+        }
+
+        pub fn memo(mut self, memo: impl Into<String>) -> Self {
+            self.body.memo = memo.into();
+            self
+        }
+
+        /// The last block height the tx can be included at.
+        /// Default: 10 blocks after the latest block when the tx is signed, none for txs
+        /// signed with [`Tx::sign`]
+        pub fn timeout_height(mut self, height: u64) -> Self {
+            self.body.timeout = Some(Timeout::Height(height));
+            self
+        }
+
+        /// Lets the tx be included at any height.
+        pub fn no_timeout(mut self) -> Self {
+            self.body.timeout = Some(Timeout::None);
+            self
+        }
+
+        /// Default: [`SignMode::Direct`]
+        pub fn sign_mode(mut self, mode: SignMode) -> Self {
+            self.sign_mode = mode;
+            self
+        }
+
+        /// Prepares the messages and carries over the memo, timeout and sign mode, which the tx
+        /// kinds leave at their defaults.
+        fn prepare_with_body(self) -> Result<PreparedTx<<Self as Broadcast>::Response>>
         where
             Self: Broadcast,
         {
-            <Self as Broadcast>::broadcast(self)
+            let body = self.body.clone();
+            let sign_mode = self.sign_mode;
+            let mut tx = self.prepare()?;
+            tx.body = body;
+            tx.sign_mode = sign_mode;
+            Ok(tx)
+        }
+
+        fn with_kind<K>(self, kind: K) -> Tx<'a, K, From> {
+            Tx {
+                client: self.client,
+                kind,
+                from: self.from,
+                fee: self.fee,
+                body: self.body,
+                sign_mode: self.sign_mode,
+            }
         }
     }
 
     impl<'a, Kind> Tx<'a, Kind, Unspecified> {
         pub fn from(self, from: &Account) -> Tx<'a, Kind, Account> {
+            self.with_from(from.clone())
+        }
+
+        /// Sends the messages from `multisig`, see [`Tx::unsigned`].
+        pub fn from_multisig(self, multisig: &Multisig) -> Tx<'a, Kind, Multisig> {
+            self.with_from(multisig.clone())
+        }
+
+        fn with_from<F>(self, from: F) -> Tx<'a, Kind, F> {
             Tx {
                 client: self.client,
                 kind: self.kind,
-                from: from.clone(),
+                from,
                 fee: self.fee,
+                body: self.body,
+                sign_mode: self.sign_mode,
             }
         }
     }
 
+    impl<'a, Kind> Tx<'a, Kind, Account> {
+        /// Signs the tx without broadcasting it. It has no timeout height unless one is set.
+        ///
+        /// The signer's next sequence is used up, so txs signed one after the other can be
+        /// broadcast in that order. If the tx is never broadcast, the next tx of the signer is
+        /// first rejected with a sequence mismatch and then re-signed with the chain's sequence.
+        pub fn sign(mut self) -> Result<SignedTx>
+        where
+            Self: Broadcast,
+        {
+            self.body.timeout.get_or_insert(Timeout::None);
+            let client = self.client;
+            client.sign_offline(self.prepare_with_body()?)
+        }
+
+        /// Broadcasts in the client [`BroadcastMode`] and returns once the tx is in a block.
+        pub fn broadcast(self) -> Result<TxResponse<<Self as Broadcast>::Response>>
+        where
+            Self: Broadcast,
+        {
+            let client = self.client;
+            client.broadcast_prepared(self.prepare_with_body()?)
+        }
+
+        /// Returns after `CheckTx`, use [`PendingTx::wait`] for the response.
+        pub fn broadcast_sync(self) -> Result<PendingTx<'a, <Self as Broadcast>::Response>>
+        where
+            Self: Broadcast,
+        {
+            let client = self.client;
+            client.submit_prepared(self.prepare_with_body()?, BroadcastMode::Sync)
+        }
+
+        /// Returns without waiting for `CheckTx`, use [`PendingTx::wait`] for the response.
+        pub fn broadcast_async(self) -> Result<PendingTx<'a, <Self as Broadcast>::Response>>
+        where
+            Self: Broadcast,
+        {
+            let client = self.client;
+            client.submit_prepared(self.prepare_with_body()?, BroadcastMode::Async)
+        }
+
+        /// Runs the tx against the latest state without committing it and returns the
+        /// decrypted response, the gas used and the events. The returned hash is never on chain.
+        pub fn simulate(self) -> Result<TxResponse<<Self as Broadcast>::Response>>
+        where
+            Self: Broadcast,
+        {
+            let client = self.client;
+            client.simulate_prepared(self.prepare_with_body()?)
+        }
+    }
+
+    impl<'a, Kind> Tx<'a, Kind, Multisig> {
+        /// Resolves the fee and sequence of the tx for the members to sign it with
+        /// [`UnsignedTx::sign_partial`]. The members always sign in legacy Amino JSON mode and
+        /// the gas cannot be estimated. It has no timeout height unless one is set, so the
+        /// members can take their time.
+        ///
+        /// The sequence is the current sequence of the multisig on chain, so unsigned txs of the
+        /// same multisig share it until one of them is broadcast.
+        pub fn unsigned(mut self) -> Result<UnsignedTx>
+        where
+            Self: Broadcast,
+        {
+            self.body.timeout.get_or_insert(Timeout::None);
+            let client = self.client;
+            client.unsigned_multisig(self.prepare_with_body()?)
+        }
+    }
+
     impl<'a, From> Tx<'a, Unspecified, From> {
         pub fn upload<P: AsRef<Path>>(self, path: P) -> Tx<'a, Upload, From> {
-            Tx {
-                client: self.client,
-                kind: Upload {
-                    path: path.as_ref().to_path_buf(),
-                },
-                from: self.from,
-                fee: self.fee,
-            }
+            self.with_kind(Upload {
+                path: path.as_ref().to_path_buf(),
+            })
         }
 
         pub fn init<M: serde::Serialize>(
@@ -109,16 +684,11 @@ pub mod builder {
             msg: M,
             code_id: CodeId,
         ) -> Tx<'a, Initialize<M>, From> {
-            Tx {
-                client: self.client,
-                kind: Initialize {
-                    msg,
-                    code_id,
-                    label: None,
-                },
-                from: self.from,
-                fee: self.fee,
-            }
+            self.with_kind(Initialize {
+                msg,
+                code_id,
+                label: None,
+            })
         }
 
         pub fn execute<M: serde::Serialize, R: serde::de::DeserializeOwned>(
@@ -126,17 +696,12 @@ pub mod builder {
             msg: M,
             contract: &Contract,
         ) -> Tx<'a, Execute<M, R>, From> {
-            Tx {
-                client: self.client,
-                kind: Execute {
-                    msg,
-                    contract: contract.clone(),
-                    sent_funds: vec![],
-                    _response: PhantomData,
-                },
-                from: self.from,
-                fee: self.fee,
-            }
+            self.with_kind(Execute {
+                msg,
+                contract: contract.clone(),
+                sent_funds: vec![],
+                _response: PhantomData,
+            })
         }
     }
 
@@ -164,16 +729,14 @@ pub mod builder {
         }
     }
 
-    impl<'a> Broadcast for Tx<'a, Upload, Account> {
+    impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Upload, F> {
         type Response = CodeId;
 
-        fn broadcast(self) -> Result<TxResponse<Self::Response>> {
+        fn prepare(self) -> Result<PreparedTx<Self::Response>> {
             let Tx {
-                client,
-                from,
-                fee,
-                kind,
+                from, fee, kind, ..
             } = self;
+            let from: Signer = from.into();
 
             use cosmrs::secret_cosmwasm::MsgStoreCode;
 
@@ -187,22 +750,30 @@ pub mod builder {
                 builder: None,
             };
 
-            let gas = fee.unwrap_or_else(|| super::gas::upload());
+            let gas = fee.or_gas(super::gas::upload());
 
-            client.broadcast_msg(msg, &from, gas)
+            Ok(PreparedTx::single(
+                msg.to_any()?,
+                from,
+                gas,
+                vec![],
+                |data| Ok(CodeId::try_from(data)?),
+            ))
         }
     }
 
-    impl<'a, M: serde::Serialize> Broadcast for Tx<'a, Initialize<M>, Account> {
+    impl<'a, M: serde::Serialize, F: Into<Signer>> Broadcast for Tx<'a, Initialize<M>, F> {
         type Response = Contract;
 
-        fn broadcast(self) -> Result<TxResponse<Self::Response>> {
+        fn prepare(self) -> Result<PreparedTx<Self::Response>> {
             let Tx {
                 client,
                 kind,
                 from,
                 fee,
+                ..
             } = self;
+            let from: Signer = from.into();
 
             use cosmrs::secret_cosmwasm::MsgInstantiateContract;
 
@@ -214,7 +785,8 @@ pub mod builder {
 
             let code_hash = client.query_code_hash_by_code_id(kind.code_id)?;
 
-            let (_, encrypted_msg) = client.encrypt_msg(&kind.msg, &code_hash, &from)?;
+            let (_, encrypted_msg) =
+                client.encrypt_msg(&kind.msg, &code_hash, from.encryption_account())?;
 
             let msg = MsgInstantiateContract {
                 sender: from.id(),
@@ -223,29 +795,38 @@ pub mod builder {
                 init_msg: encrypted_msg,
             };
 
-            let gas = fee.unwrap_or_else(|| super::gas::init());
+            let gas = fee.or_gas(super::gas::init());
 
-            client
-                .broadcast_msg(msg, &from, gas)
-                .map(|tx: TxResponse<ContractInit>| tx.map(|c| c.into_contract(code_hash)))
+            Ok(PreparedTx::single(
+                msg.to_any()?,
+                from,
+                gas,
+                vec![],
+                |data| Ok(ContractInit::try_from(data)?.into_contract(code_hash)),
+            ))
         }
     }
 
-    impl<'a, M: serde::Serialize, R: serde::de::DeserializeOwned> Broadcast
-        for Tx<'a, Execute<M, R>, Account>
+    impl<'a, M: serde::Serialize, R: serde::de::DeserializeOwned, F: Into<Signer>> Broadcast
+        for Tx<'a, Execute<M, R>, F>
     {
         type Response = R;
 
-        fn broadcast(self) -> Result<TxResponse<Self::Response>> {
+        fn prepare(self) -> Result<PreparedTx<Self::Response>> {
             let Tx {
                 client,
                 kind,
                 from,
                 fee,
+                ..
             } = self;
+            let from: Signer = from.into();
 
-            let (nonce, encrypted_msg) =
-                client.encrypt_msg(&kind.msg, kind.contract.code_hash(), &from)?;
+            let (nonce, encrypted_msg) = client.encrypt_msg(
+                &kind.msg,
+                kind.contract.code_hash(),
+                from.encryption_account(),
+            )?;
 
             use cosmrs::secret_cosmwasm::MsgExecuteContract;
             let msg = MsgExecuteContract {
@@ -255,18 +836,22 @@ pub mod builder {
                 sent_funds: kind.sent_funds,
             };
 
-            let decrypter = client.decrypter(&nonce, &from)?;
+            let decrypter = client.decrypter(&nonce, from.encryption_account())?;
 
-            let gas = fee.unwrap_or_else(|| super::gas::exec());
+            let gas = fee.or_gas(super::gas::exec());
 
-            client
-                .broadcast_msg_raw(msg, &from, gas)
-                .map(|btr| btr.with_error_decrypt(decrypter))
-                .and_then(Result::from)
-                .and_then(|tx| tx.try_map(|cit| decrypter.decrypt(&cit)))
-                .and_then(|tx| tx.try_map(|plt| String::from_utf8(plt)))
-                .and_then(|tx| tx.try_map(|b64| base64::decode(b64)))
-                .and_then(|tx| tx.try_map(|buf| serde_json::from_slice(&buf)))
+            Ok(PreparedTx::single(
+                msg.to_any()?,
+                from,
+                gas,
+                vec![decrypter],
+                move |cit| {
+                    let plt = decrypter.decrypt(&cit)?;
+                    let b64 = String::from_utf8(plt)?;
+                    let buf = base64::decode(b64)?;
+                    Ok(serde_json::from_slice(&buf)?)
+                },
+            ))
         }
     }
 
@@ -275,7 +860,23 @@ pub mod builder {
             client,
             kind: Unspecified,
             from: Unspecified,
-            fee: None,
+            fee: FeeOptions::default(),
+            body: BodyOptions::default(),
+            sign_mode: SignMode::Direct,
+        }
+    }
+
+    fn any(type_url: &str, msg: impl prost::Message) -> cosmrs::Any {
+        cosmrs::Any {
+            type_url: type_url.to_owned(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn proto_coin(coin: cosmwasm_std::Coin) -> cosmrs::proto::cosmos::base::v1beta1::Coin {
+        cosmrs::proto::cosmos::base::v1beta1::Coin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
         }
     }
 }
@@ -285,97 +886,696 @@ impl super::Client {
         builder::new(self)
     }
 
-    fn broadcast_msg_raw<M>(
+    /// Polls for a tx by its hash until it is included in a block or `timeout` elapses and
+    /// returns the raw data of each message, or the error the tx failed with.
+    ///
+    /// Unlike [`PendingTx::wait`](builder::PendingTx::wait), which knows the messages it sent,
+    /// the response is not parsed: contract responses and errors stay encrypted, as only the
+    /// sender's client holds the keys to decrypt them.
+    pub fn wait_for_tx(&self, hash: &str, timeout: Duration) -> Result<TxResponse<Vec<Vec<u8>>>> {
+        let btr = self.wait_for_tx_raw(hash, timeout)?;
+        msg_data_response(btr)
+    }
+
+    /// Broadcasts a tx signed with [`Tx::sign`](builder::Tx::sign) in the client
+    /// [`BroadcastMode`] and returns once it is in a block. The response holds the data of
+    /// each message, contract responses stay encrypted.
+    pub fn broadcast_raw(&self, tx: &builder::SignedTx) -> Result<TxResponse<Vec<Vec<u8>>>> {
+        let btr = match self.broadcast_mode() {
+            BroadcastMode::Commit => {
+                let res =
+                    self.block_on(self.rpc.broadcast_tx_commit(tx.as_bytes().to_vec().into()))?;
+                broadcast_tx_response(res)
+            }
+            // waits for inclusion anyway, so check tx errors are reported in async mode too
+            BroadcastMode::Sync | BroadcastMode::Async => {
+                let hash = self.submit_tx_bytes(tx.as_bytes().to_vec(), BroadcastMode::Sync)?;
+                self.wait_for_tx_raw(&hash, TX_INCLUSION_TIMEOUT)?
+            }
+        };
+
+        msg_data_response(btr)
+    }
+
+    /// Decrypts the response `data` of the contract execution at `index` in `tx`, e.g. of a
+    /// [`Multisig`](crate::Multisig) tx sent with [`Client::broadcast_raw`], whose message was
+    /// encrypted with `account`.
+    pub fn decrypt_msg_response<R: serde::de::DeserializeOwned>(
         &self,
-        msg: M,
+        tx: &builder::SignedTx,
+        index: usize,
+        data: &[u8],
         account: &Account,
-        gas: Fee,
-    ) -> Result<BroadcastTxResponse>
-    where
-        M: Msg,
-    {
-        const HEIGHT_TIMEOUT_INTERVAL: u32 = 10;
+    ) -> Result<R> {
+        use cosmrs::{
+            proto::cosmos::tx::v1beta1 as proto, secret_cosmwasm::MsgExecuteContract, tx::Msg,
+        };
 
-        let last_block_height = self.last_block_height()?;
-        let account_info = self.query_account_info(account)?;
+        let tx_raw = proto::TxRaw::decode(tx.as_bytes())?;
+        let body = proto::TxBody::decode(tx_raw.body_bytes.as_slice())?;
+        let any = body
+            .messages
+            .get(index)
+            .filter(|any| any.type_url == "/secret.compute.v1beta1.MsgExecuteContract")
+            .ok_or_else(|| {
+                Error::InvalidSignedTx(format!("message {index} is not a contract execution"))
+            })?;
+        let msg = MsgExecuteContract::from_any(any)?;
+        // the encrypted message starts with its nonce
+        let nonce = msg
+            .msg
+            .get(..32)
+            .map(crate::crypto::clone_into_key)
+            .ok_or_else(|| Error::InvalidSignedTx(format!("message {index} is not encrypted")))?;
+
+        let plt = self.decrypter(&nonce, account)?.decrypt(data)?;
+        let b64 = String::from_utf8(plt)?;
+        let buf = base64::decode(b64)?;
+        Ok(serde_json::from_slice(&buf)?)
+    }
 
-        let body = Body::new(
-            vec![msg.to_any()?],
-            String::new(),
-            last_block_height + HEIGHT_TIMEOUT_INTERVAL,
-        );
+    /// Signs and broadcasts all messages of `tx` in a single tx, which fails if any message
+    /// fails.
+    fn broadcast_prepared<R>(&self, tx: PreparedTx<R>) -> Result<TxResponse<R>> {
+        let btr = match self.broadcast_mode() {
+            BroadcastMode::Commit => self.sign_and_submit(&tx, |tx_raw| {
+                let res = self.block_on(tx_raw.broadcast_commit(&self.rpc))?;
+                match broadcast_tx_response(res) {
+                    BroadcastTxResponse::TxCheckError(log) => Err(Error::BroadcastTxCheck(log)),
+                    btr => Ok(btr),
+                }
+            })?,
+            // waits for inclusion anyway, so check tx errors are reported in async mode too
+            BroadcastMode::Sync | BroadcastMode::Async => {
+                let hash = self.sign_and_submit(&tx, |tx_raw| {
+                    self.submit_tx_raw(tx_raw, BroadcastMode::Sync)
+                })?;
+                self.wait_for_tx_raw(&hash, TX_INCLUSION_TIMEOUT)?
+            }
+        };
+
+        let tx_res = btr.into_result_with_decrypt(&tx.decrypters)?;
+        (tx.response)(tx_res)
+    }
+
+    fn submit_prepared<R>(
+        &self,
+        tx: PreparedTx<R>,
+        mode: BroadcastMode,
+    ) -> Result<builder::PendingTx<'_, R>> {
+        let hash = self.sign_and_submit(&tx, |tx_raw| self.submit_tx_raw(tx_raw, mode))?;
+        Ok(builder::PendingTx {
+            client: self,
+            hash,
+            decrypters: tx.decrypters,
+            response: tx.response,
+        })
+    }
 
-        let auth_info = SignerInfo::single_direct(
-            Some(account.signing_key().public_key()),
-            account_info.sequence_number,
-        )
-        .auth_info(gas);
+    /// Signs `tx` with the locally tracked sequences of its signers and passes it to `submit`.
+    /// A tx rejected with a sequence mismatch, by the node or by the simulation of
+    /// [`Gas::Auto`], is re-signed with the sequence the node expects and submitted again.
+    fn sign_and_submit<R, T>(
+        &self,
+        tx: &PreparedTx<R>,
+        submit: impl Fn(Raw) -> Result<T>,
+    ) -> Result<T> {
+        const MAX_ATTEMPTS: usize = 3;
+
+        let mut attempt = 1;
+        loop {
+            let signers = self.signers(&tx.from, tx.fee.payer.as_ref())?;
+            match self.sign_prepared(tx, &signers).and_then(&submit) {
+                Ok(res) => {
+                    self.advance_sequences(&signers);
+                    return Ok(res);
+                }
+                Err(err) => {
+                    let mismatch = match &err {
+                        // simulation failures are deliver errors
+                        Error::BroadcastTxCheck(log) | Error::BroadcastTxDeliver(log) => {
+                            parse_sequence_mismatch(log)
+                        }
+                        _ => None,
+                    };
+                    match mismatch {
+                        Some(expected) if attempt < MAX_ATTEMPTS => {
+                            self.resync_sequences(&signers, expected);
+                            attempt += 1;
+                        }
+                        _ => {
+                            // the tx may or may not have made it into the mempool
+                            self.resync_sequences(&signers, None);
+                            return Err(err);
+                        }
+                    }
+                }
+            }
+        }
+    }
 
-        let sign_doc = SignDoc::new(&body, &auth_info, &chain_id(), account_info.account_number)?;
+    fn sign_offline<R>(&self, tx: PreparedTx<R>) -> Result<builder::SignedTx> {
+        let signers = self.signers(&tx.from, tx.fee.payer.as_ref())?;
+        let bytes = self.sign_prepared(&tx, &signers)?.to_bytes()?;
+        self.advance_sequences(&signers);
+        Ok(builder::SignedTx::new(bytes))
+    }
+
+    fn simulate_prepared<R>(&self, tx: PreparedTx<R>) -> Result<TxResponse<R>> {
+        let body = self.tx_body(tx.msgs.clone(), &tx.body)?;
+        let signers = self.signers(&tx.from, tx.fee.payer.as_ref())?;
+        let tx_raw = self.sign_tx(&body, gas::simulation(&tx.fee), &signers, tx.sign_mode)?;
+        let tx_res = self
+            .simulate_tx_raw(tx_raw)?
+            .into_result_with_decrypt(&tx.decrypters)?;
+        (tx.response)(tx_res)
+    }
+
+    /// Resolves the fee of `tx`, simulating it first for [`Gas::Auto`], and signs it.
+    fn sign_prepared<R>(
+        &self,
+        tx: &PreparedTx<R>,
+        signers: &[(&Account, Sequence)],
+    ) -> Result<Raw> {
+        let PreparedTx {
+            msgs,
+            fee,
+            body,
+            sign_mode,
+            decrypters,
+            ..
+        } = tx;
+
+        let body = self.tx_body(msgs.clone(), body)?;
+        let signed_fee = self.resolve_fee(fee, || {
+            let simulated = self.sign_tx(&body, gas::simulation(fee), signers, *sign_mode)?;
+            Ok(self
+                .simulate_tx_raw(simulated)?
+                .into_result_with_decrypt(decrypters)?
+                .gas_used)
+        })?;
+
+        self.sign_tx(&body, signed_fee, signers, *sign_mode)
+    }
 
-        let tx_raw = sign_doc.sign(&account.signing_key())?;
+    /// The fee of a tx at its gas price, `simulate` returns the gas used for [`Gas::Auto`].
+    fn resolve_fee(&self, fee: &TxFee, simulate: impl FnOnce() -> Result<u64>) -> Result<Fee> {
+        let gas_price = fee.gas_price.clone().unwrap_or_else(|| self.gas_price());
+
+        let (amount, gas_limit) = match fee.gas {
+            Gas::Fixed { amount, gas } => (amount, gas),
+            Gas::Limit(gas) => (gas_price.fee_amount(gas)?, gas),
+            Gas::Auto(multiplier) => {
+                check_gas_multiplier(multiplier)?;
+                let gas_limit = (simulate()? as f64 * multiplier).ceil() as u64;
+                (gas_price.fee_amount(gas_limit)?, gas_limit)
+            }
+        };
 
-        let res = self.block_on(tx_raw.broadcast_commit(&self.rpc))?;
+        let coin = cosmrs::Coin {
+            denom: gas_price.denom().parse()?,
+            amount: amount.into(),
+        };
+        let mut resolved = Fee::from_amount_and_gas(coin, gas_limit);
+        resolved.payer = fee.payer.as_ref().map(Account::id);
+        resolved.granter = fee.granter.as_ref().map(Account::id);
 
-        Ok(broadcast_tx_response(M::Proto::TYPE_URL, res))
+        Ok(resolved)
     }
 
-    fn broadcast_msg<T, M>(&self, msg: M, account: &Account, gas: Fee) -> Result<TxResponse<T>>
-    where
-        T: TryFrom<Vec<u8>>,
-        crate::Error: From<T::Error>,
-        M: Msg,
-    {
-        self.broadcast_msg_raw(msg, account, gas)
-            .and_then(Result::from)
-            .and_then(|tx| tx.try_map(T::try_from))
+    /// Resolves everything the members of a multisig sign: the body, the fee and the sequence
+    /// of the multisig account, which is always queried as the client cannot tell whether
+    /// earlier multisig txs were broadcast.
+    fn unsigned_multisig<R>(&self, tx: PreparedTx<R>) -> Result<builder::UnsignedTx> {
+        let multisig = match tx.from {
+            builder::Signer::Multisig(multisig) => multisig,
+            builder::Signer::Account(account) => {
+                return Err(Error::Multisig(format!(
+                    "{} is not a multisig",
+                    account.human_address()
+                )))
+            }
+        };
+        if tx.fee.payer.is_some() {
+            return Err(Error::Multisig("a fee payer cannot co-sign".to_owned()));
+        }
+
+        let body = self.tx_body(tx.msgs, &tx.body)?;
+        let fee = self.resolve_fee(&tx.fee, || {
+            Err(Error::Multisig(
+                "the gas cannot be estimated without the signatures".to_owned(),
+            ))
+        })?;
+
+        let info = self.query_account(multisig.id().as_ref())?;
+        let sequence = Sequence {
+            account_number: info.account_number,
+            sequence: info.sequence_number,
+        };
+
+        Ok(builder::UnsignedTx {
+            body,
+            fee: fee.into(),
+            chain_id: self.chain_id()?.to_string(),
+            sequence,
+            multisig,
+        })
+    }
+
+    fn tx_body(&self, msgs: Vec<cosmrs::Any>, options: &BodyOptions) -> Result<Body> {
+        let timeout_height = match options.timeout.unwrap_or(Timeout::Blocks(10)) {
+            Timeout::Blocks(blocks) => u64::from(self.last_block_height()?) + blocks,
+            Timeout::Height(height) => height,
+            // zero is no timeout
+            Timeout::None => 0,
+        };
+
+        Ok(Body::new(
+            msgs,
+            options.memo.clone(),
+            super::block::to_height(timeout_height)?,
+        ))
+    }
+
+    /// The signing accounts in the order the SDK expects signatures: the message signer,
+    /// then the fee payer.
+    fn signers<'a>(
+        &self,
+        signer: &'a builder::Signer,
+        payer: Option<&'a Account>,
+    ) -> Result<Vec<(&'a Account, Sequence)>> {
+        let account = match signer {
+            builder::Signer::Account(account) => account,
+            builder::Signer::Multisig(multisig) => {
+                return Err(Error::Multisig(format!(
+                    "{} signs with partial signatures",
+                    multisig.human_address()
+                )))
+            }
+        };
+
+        std::iter::once(account)
+            .chain(payer.filter(|payer| payer.id() != account.id()))
+            .map(|signer| Ok((signer, self.sequence(&signer.id())?)))
+            .collect()
+    }
+
+    /// The next sequence of `address`, queried from the node only when it is not tracked yet.
+    fn sequence(&self, address: &AccountId) -> Result<Sequence> {
+        let address = address.to_string();
+        if let Some(sequence) = self.sequences.borrow().get(&address) {
+            return Ok(*sequence);
+        }
+
+        let info = self.query_account(&address)?;
+        let sequence = Sequence {
+            account_number: info.account_number,
+            sequence: info.sequence_number,
+        };
+        self.sequences.borrow_mut().insert(address, sequence);
+
+        Ok(sequence)
+    }
+
+    fn advance_sequences(&self, signers: &[(&Account, Sequence)]) {
+        let mut sequences = self.sequences.borrow_mut();
+        for (account, sequence) in signers {
+            sequences.insert(
+                account.id().to_string(),
+                Sequence {
+                    sequence: sequence.sequence + 1,
+                    ..*sequence
+                },
+            );
+        }
+    }
+
+    /// Sets the sequence of a single signer to the `expected` sequence of a mismatch error,
+    /// otherwise drops the signers so that their sequences are queried again.
+    fn resync_sequences(&self, signers: &[(&Account, Sequence)], expected: Option<u64>) {
+        let mut sequences = self.sequences.borrow_mut();
+        match (signers, expected) {
+            ([(account, sequence)], Some(expected)) => {
+                sequences.insert(
+                    account.id().to_string(),
+                    Sequence {
+                        sequence: expected,
+                        ..*sequence
+                    },
+                );
+            }
+            _ => {
+                for (account, _) in signers {
+                    sequences.remove(&account.id().to_string());
+                }
+            }
+        }
+    }
+
+    fn sign_tx(
+        &self,
+        body: &Body,
+        fee: Fee,
+        signers: &[(&Account, Sequence)],
+        sign_mode: SignMode,
+    ) -> Result<Raw> {
+        match sign_mode {
+            SignMode::Direct => self.sign_tx_direct(body, fee, signers),
+            SignMode::LegacyAminoJson => self.sign_tx_amino(body, fee, signers),
+        }
+    }
+
+    fn sign_tx_direct(
+        &self,
+        body: &Body,
+        fee: Fee,
+        signers: &[(&Account, Sequence)],
+    ) -> Result<Raw> {
+        let signer_infos = signers
+            .iter()
+            .map(|(account, sequence)| {
+                SignerInfo::single_direct(
+                    Some(account.signing_key().public_key()),
+                    sequence.sequence,
+                )
+            })
+            .collect();
+
+        let auth_info = AuthInfo { signer_infos, fee };
+
+        let chain_id = self.chain_id()?;
+
+        let mut tx_raw = None;
+        let mut signatures = Vec::with_capacity(signers.len());
+        for (account, sequence) in signers {
+            let sign_doc = SignDoc::new(body, &auth_info, &chain_id, sequence.account_number)?;
+            let sign_doc_bytes = sign_doc.clone().into_bytes()?;
+            signatures.push(
+                account
+                    .signing_key()
+                    .sign(&sign_doc_bytes)?
+                    .as_ref()
+                    .to_vec(),
+            );
+            tx_raw.get_or_insert(cosmrs::proto::cosmos::tx::v1beta1::TxRaw {
+                body_bytes: sign_doc.body_bytes,
+                auth_info_bytes: sign_doc.auth_info_bytes,
+                signatures: vec![],
+            });
+        }
+
+        let mut tx_raw = tx_raw.expect("a tx has at least one signer");
+        tx_raw.signatures = signatures;
+
+        Ok(tx_raw.into())
+    }
+
+    /// Signs the Amino JSON sign doc of the tx, which the chain rebuilds from the body and
+    /// auth info to verify the signatures.
+    fn sign_tx_amino(
+        &self,
+        body: &Body,
+        fee: Fee,
+        signers: &[(&Account, Sequence)],
+    ) -> Result<Raw> {
+        use cosmrs::proto::cosmos::tx::v1beta1 as proto;
+
+        let fee = proto::Fee::from(fee);
+        let chain_id = self.chain_id()?;
+
+        let mut signer_infos = Vec::with_capacity(signers.len());
+        let mut signatures = Vec::with_capacity(signers.len());
+        for (account, sequence) in signers {
+            signer_infos.push(proto::SignerInfo {
+                public_key: Some(account.signing_key().public_key().to_any()?),
+                mode_info: Some(amino::mode_info()),
+                sequence: sequence.sequence,
+            });
+            let sign_doc_bytes = amino::sign_doc_bytes(
+                chain_id.as_str(),
+                sequence.account_number,
+                sequence.sequence,
+                &fee,
+                body,
+            )?;
+            signatures.push(
+                account
+                    .signing_key()
+                    .sign(&sign_doc_bytes)?
+                    .as_ref()
+                    .to_vec(),
+            );
+        }
+
+        let auth_info = proto::AuthInfo {
+            signer_infos,
+            fee: Some(fee),
+        };
+
+        Ok(proto::TxRaw {
+            body_bytes: body.clone().into_bytes()?,
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures,
+        }
+        .into())
+    }
+
+    /// Sends a signed tx without waiting for it to be committed and returns its hash.
+    fn submit_tx_raw(&self, tx_raw: Raw, mode: BroadcastMode) -> Result<String> {
+        self.submit_tx_bytes(tx_raw.to_bytes()?, mode)
+    }
+
+    fn submit_tx_bytes(&self, tx: Vec<u8>, mode: BroadcastMode) -> Result<String> {
+        let tx = tx.into();
+        match mode {
+            BroadcastMode::Async => {
+                let res = self.block_on(self.rpc.broadcast_tx_async(tx))?;
+                Ok(res.hash.to_string())
+            }
+            BroadcastMode::Sync | BroadcastMode::Commit => {
+                let res = self.block_on(self.rpc.broadcast_tx_sync(tx))?;
+                if res.code.is_err() {
+                    return Err(Error::BroadcastTxCheck(res.log.to_string()));
+                }
+                Ok(res.hash.to_string())
+            }
+        }
+    }
+
+    fn wait_for_tx_raw(
+        &self,
+        hash: &str,
+        timeout: Duration,
+    ) -> Result<BroadcastTxResponse<Vec<MsgData>>> {
+        let res = self.poll_tx(parse_tx_hash(hash)?, timeout)?;
+        Ok(deliver_tx_response(
+            res.hash.to_string(),
+            res.height.value(),
+            res.tx_result,
+        ))
+    }
+
+    fn poll_tx(
+        &self,
+        hash: Hash,
+        timeout: Duration,
+    ) -> Result<cosmrs::rpc::endpoint::tx::Response> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+        let start = Instant::now();
+
+        loop {
+            match self.block_on(self.rpc.tx(hash, false)) {
+                Ok(res) => return Ok(res),
+                Err(err) if !is_tx_not_found(&err, &hash) => return Err(err.into()),
+                Err(_) if start.elapsed() < timeout => {
+                    self.block_on(tokio::time::sleep(POLL_INTERVAL))
+                }
+                Err(_) => return Err(Error::TxTimeout(hash.to_string(), timeout.as_secs())),
+            }
+        }
+    }
+
+    /// Runs a signed tx through `cosmos.tx.v1beta1.Service/Simulate`, which executes it
+    /// against the latest state without committing anything.
+    fn simulate_tx_raw(&self, tx_raw: Raw) -> Result<BroadcastTxResponse<Vec<MsgData>>> {
+        let tx_bytes = tx_raw.to_bytes()?;
+        let hash = tx_hash(&tx_bytes);
+        let req = SimulateRequest {
+            tx_bytes,
+            ..Default::default()
+        };
+        let res = match self
+            .abci_query::<_, SimulateResponse>("/cosmos.tx.v1beta1.Service/Simulate", req)
+        {
+            Ok(res) => res,
+            Err(Error::AbciQuery(log)) => return Ok(deliver_error(log)),
+            Err(err) => return Err(err),
+        };
+
+        let result = res.result.unwrap_or_default();
+        let response = TxMsgData::decode(result.data.as_slice())?.data;
+
+        Ok(BroadcastTxResponse::Delivered(TxResponse {
+            response: Some(response),
+            hash,
+            height: u64::from(self.last_block_height()?),
+            gas_used: res.gas_info.map_or(0, |info| info.gas_used),
+            events: result.events.into_iter().map(Event::from).collect(),
+        }))
+    }
+}
+
+/// How long [`Tx::broadcast`](builder::Tx::broadcast) waits for inclusion outside of commit mode.
+const TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn parse_tx_hash(hash: &str) -> Result<Hash> {
+    hash.parse()
+        .map_err(|_| Error::InvalidTxHash(hash.to_owned()))
+}
+
+/// Whether `err` is the answer of the node for a tx that is not indexed yet: an internal
+/// JSON-RPC error with the data `tx (<HASH>) not found`.
+fn is_tx_not_found(err: &cosmrs::rpc::Error, hash: &Hash) -> bool {
+    use cosmrs::rpc::{error::ErrorDetail, response_error::Code};
+
+    match err.detail() {
+        ErrorDetail::Response(res) => {
+            res.source.code() == Code::InternalError
+                && res.source.data() == Some(format!("tx ({hash}) not found").as_str())
+        }
+        _ => false,
+    }
+}
+
+/// The hash of a tx as the node reports it: the upper case hex SHA-256 of its bytes.
+fn tx_hash(tx_bytes: &[u8]) -> String {
+    hex::encode_upper(Sha256::digest(tx_bytes))
+}
+
+/// The account number and next sequence of a signer, tracked by the client between txs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Sequence {
+    account_number: u64,
+    sequence: u64,
+}
+
+/// Whether a check tx or simulation error `log` is a sequence mismatch, with the sequence the
+/// node expects if the log contains it, e.g. "account sequence mismatch, expected 5, got 3:
+/// incorrect account sequence".
+fn parse_sequence_mismatch(log: &str) -> Option<Option<u64>> {
+    let (_, rest) = log.split_once("account sequence mismatch")?;
+    let expected = rest
+        .split_once("expected ")
+        .and_then(|(_, rest)| rest.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|expected| expected.parse().ok());
+    Some(expected)
+}
+
+/// A [`Gas::Auto`] multiplier has to be finite and at least 1, anything below would set the gas
+/// limit under the simulated gas used, down to 0 for NaN or negative multipliers.
+fn check_gas_multiplier(multiplier: f64) -> Result<()> {
+    if multiplier.is_finite() && multiplier >= 1.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidGasMultiplier(multiplier))
     }
 }
 
-enum BroadcastTxResponse {
+/// The fee settings of a tx builder, the gas defaults to the gas of the tx kind.
+#[derive(Clone, Default)]
+pub(crate) struct FeeOptions {
+    gas: Option<Gas>,
+    gas_price: Option<GasPrice>,
+    payer: Option<Account>,
+    granter: Option<Account>,
+}
+
+impl FeeOptions {
+    pub(crate) fn or_gas(self, gas: Gas) -> TxFee {
+        TxFee {
+            gas: self.gas.unwrap_or(gas),
+            gas_price: self.gas_price,
+            payer: self.payer,
+            granter: self.granter,
+        }
+    }
+}
+
+/// The memo and timeout of a tx body.
+#[derive(Clone, Default)]
+pub(crate) struct BodyOptions {
+    memo: String,
+    /// Defaults to 10 blocks, unless the way the tx is sent picks another default.
+    timeout: Option<Timeout>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Timeout {
+    /// This many blocks after the latest block when the tx is signed.
+    Blocks(u64),
+    Height(u64),
+    None,
+}
+
+/// The fee of a tx, resolved into a [`Fee`] right before signing.
+pub(crate) struct TxFee {
+    gas: Gas,
+    /// Defaults to the client gas price.
+    gas_price: Option<GasPrice>,
+    payer: Option<Account>,
+    granter: Option<Account>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Gas {
+    /// A fixed fee amount and gas limit.
+    Fixed { amount: u64, gas: u64 },
+    /// A gas limit paid for at the gas price.
+    Limit(u64),
+    /// The simulated gas used times this multiplier, paid for at the gas price.
+    Auto(f64),
+}
+
+pub(crate) enum BroadcastTxResponse<T = Vec<u8>> {
     TxCheckError(String),
     TxDeliverErrorPlain(String),
     TxDeliverErrorEncrypted(String, Vec<u8>),
-    Delivered(TxResponse<Vec<u8>>),
+    Delivered(TxResponse<T>),
 }
 
-impl BroadcastTxResponse {
-    fn into_result_with_decrypt(
+impl<T> BroadcastTxResponse<T> {
+    /// Decrypts an encrypted contract error with the first of `decrypters` that succeeds.
+    pub(crate) fn into_result_with_decrypt(
         self,
-        with_decrypt: Option<Decrypter>,
-    ) -> Result<TxResponse<Vec<u8>>> {
+        decrypters: &[Decrypter],
+    ) -> Result<TxResponse<T>> {
         match self {
             BroadcastTxResponse::TxCheckError(err) => Err(Error::BroadcastTxCheck(err)),
             BroadcastTxResponse::TxDeliverErrorPlain(err) => Err(Error::BroadcastTxDeliver(err)),
-            BroadcastTxResponse::TxDeliverErrorEncrypted(log, ciphertext) => Err(with_decrypt
-                .map(|decrypter| decrypter.decrypt(&ciphertext))
-                .transpose()?
+            BroadcastTxResponse::TxDeliverErrorEncrypted(log, ciphertext) => Err(decrypters
+                .iter()
+                .find_map(|decrypter| decrypter.decrypt(&ciphertext).ok())
                 .map(|plaintext| String::from_utf8(plaintext))
                 .transpose()?
                 .map_or_else(|| Error::BroadcastTxDeliver(log), Error::BroadcastTxDeliver)),
             BroadcastTxResponse::Delivered(tx_res) => Ok(tx_res),
         }
     }
-
-    fn with_error_decrypt<'a>(self, decrypt: Decrypter) -> WithErrorDecryption {
-        WithErrorDecryption { decrypt, btr: self }
-    }
-}
-
-impl From<BroadcastTxResponse> for Result<TxResponse<Vec<u8>>> {
-    fn from(btr: BroadcastTxResponse) -> Self {
-        btr.into_result_with_decrypt(None)
-    }
-}
-
-struct WithErrorDecryption {
-    decrypt: Decrypter,
-    btr: BroadcastTxResponse,
 }
 
-impl From<WithErrorDecryption> for Result<TxResponse<Vec<u8>>> {
-    fn from(wed: WithErrorDecryption) -> Self {
-        wed.btr.into_result_with_decrypt(Some(wed.decrypt))
+impl TxResponse<Vec<MsgData>> {
+    fn find_msg_data(self, msg_type: &str) -> TxResponse<Vec<u8>> {
+        TxResponse {
+            response: self
+                .response
+                .and_then(|msgs| msgs.into_iter().find(|msg| msg.msg_type == msg_type))
+                .map(|msg| msg.data),
+            hash: self.hash,
+            height: self.height,
+            gas_used: self.gas_used,
+            events: self.events,
+        }
     }
 }
 
@@ -385,91 +1585,219 @@ fn try_extract_encrypted_error(log: &str) -> Option<Vec<u8>> {
         .and_then(|(b64, _)| base64::decode(b64.trim()).ok())
 }
 
-fn broadcast_tx_response(msg_type: &str, bcast_res: BroadcastResponse) -> BroadcastTxResponse {
+/// The data of each message of a tx sent without decrypters.
+fn msg_data_response(btr: BroadcastTxResponse<Vec<MsgData>>) -> Result<TxResponse<Vec<Vec<u8>>>> {
+    let tx_res = btr.into_result_with_decrypt(&[])?;
+    Ok(tx_res.map(|msgs| msgs.into_iter().map(|msg| msg.data).collect()))
+}
+
+fn deliver_error(log: String) -> BroadcastTxResponse<Vec<MsgData>> {
+    if let Some(ciphertext) = try_extract_encrypted_error(&log) {
+        BroadcastTxResponse::TxDeliverErrorEncrypted(log, ciphertext)
+    } else {
+        BroadcastTxResponse::TxDeliverErrorPlain(log)
+    }
+}
+
+fn broadcast_tx_response(bcast_res: BroadcastResponse) -> BroadcastTxResponse<Vec<MsgData>> {
     if bcast_res.check_tx.code.is_err() {
         return BroadcastTxResponse::TxCheckError(bcast_res.check_tx.log.to_string());
     }
 
-    if bcast_res.deliver_tx.code.is_err() {
-        let log = bcast_res.deliver_tx.log.to_string();
-        return if let Some(ciphertext) = try_extract_encrypted_error(&log) {
-            BroadcastTxResponse::TxDeliverErrorEncrypted(log, ciphertext)
-        } else {
-            BroadcastTxResponse::TxDeliverErrorPlain(log)
-        };
+    deliver_tx_response(
+        bcast_res.hash.to_string(),
+        bcast_res.height.value(),
+        bcast_res.deliver_tx,
+    )
+}
+
+fn deliver_tx_response(
+    hash: String,
+    height: u64,
+    deliver_tx: DeliverTx,
+) -> BroadcastTxResponse<Vec<MsgData>> {
+    if deliver_tx.code.is_err() {
+        return deliver_error(deliver_tx.log.to_string());
     }
 
-    let gas_used = bcast_res.deliver_tx.gas_used.into();
-    let events = bcast_res
-        .deliver_tx
-        .events
-        .into_iter()
-        .map(|e| {
-            let attrs = e
-                .attributes
-                .into_iter()
-                .map(|a| (a.key.to_string(), a.value.to_string()))
-                .collect();
-            Event {
-                _type: e.type_str,
-                attrs,
-            }
-        })
-        .collect();
-
-    let response = bcast_res
-        .deliver_tx
-        .data
-        .and_then(|data| {
-            use cosmrs::proto::cosmos::base::abci::v1beta1::TxMsgData;
-            TxMsgData::decode(data.as_bytes())
-                .expect("unexpected data in response")
-                .data
-                .into_iter()
-                .find(|msg| msg.msg_type == msg_type)
-        })
-        .map(|msg| msg.data);
+    let gas_used = deliver_tx.gas_used.into();
+    let events = deliver_tx.events.into_iter().map(Event::from).collect();
+
+    let response = deliver_tx.data.map(|data| {
+        TxMsgData::decode(data.as_bytes())
+            .expect("unexpected data in response")
+            .data
+    });
 
     BroadcastTxResponse::Delivered(TxResponse {
         response,
+        hash,
+        height,
         gas_used,
         events,
     })
 }
 
-fn chain_id() -> cosmrs::tendermint::chain::Id {
-    crate::consts::CHAIN_ID.parse().expect("invalid chain id")
-}
-
 mod gas {
     use cosmrs::tx::Fee;
 
-    use crate::consts;
+    use crate::{consts, Account};
+
+    use super::{Gas, TxFee};
 
-    fn uscrt(amount: u64) -> cosmrs::Coin {
+    /// Simulation runs with an infinite gas meter and skips the fee checks. The payer and
+    /// granter of `fee` are kept, the SDK counts the payer among the signers.
+    pub fn simulation(fee: &TxFee) -> Fee {
         let denom = consts::COIN_DENOM
             .parse()
             .expect("invalid coin denomination");
 
-        cosmrs::Coin {
+        let coin = cosmrs::Coin {
             denom,
-            amount: amount.into(),
-        }
+            amount: 0u64.into(),
+        };
+
+        let mut simulation = Fee::from_amount_and_gas(coin, 0u64);
+        simulation.payer = fee.payer.as_ref().map(Account::id);
+        simulation.granter = fee.granter.as_ref().map(Account::id);
+        simulation
+    }
+
+    pub fn upload() -> Gas {
+        Gas::Limit(consts::UPLOAD_GAS)
+    }
+
+    pub fn init() -> Gas {
+        Gas::Limit(consts::INIT_GAS)
+    }
+
+    pub fn exec() -> Gas {
+        Gas::Limit(consts::EXEC_GAS)
+    }
+
+    pub fn send() -> Gas {
+        Gas::Limit(consts::SEND_GAS)
     }
 
-    pub fn fee(amount: u64, gas: u64) -> Fee {
-        Fee::from_amount_and_gas(uscrt(amount), gas)
+    pub fn gov() -> Gas {
+        Gas::Limit(consts::GOV_GAS)
     }
 
-    pub fn upload() -> Fee {
-        fee(consts::UPLOAD_AMOUNT, consts::UPLOAD_GAS)
+    pub fn staking() -> Gas {
+        Gas::Limit(consts::STAKING_GAS)
     }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sequence_mismatch_logs() {
+        assert_eq!(
+            parse_sequence_mismatch(
+                "account sequence mismatch, expected 5, got 3: incorrect account sequence"
+            ),
+            Some(Some(5))
+        );
+        assert_eq!(
+            parse_sequence_mismatch("account sequence mismatch: incorrect account sequence"),
+            Some(None)
+        );
+        assert_eq!(parse_sequence_mismatch("insufficient fees"), None);
+    }
+
+    #[test]
+    fn gas_multipliers() {
+        assert!(check_gas_multiplier(1.0).is_ok());
+        assert!(check_gas_multiplier(1.3).is_ok());
+        for multiplier in [0.0, 0.9, -1.5, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                check_gas_multiplier(multiplier),
+                Err(Error::InvalidGasMultiplier(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn unsigned_tx_export_and_member_bounds() {
+        let (a, b) = (crate::a(), crate::b());
+        let multisig = crate::Multisig::new(1, &[a.public_key(), b.public_key()]).unwrap();
+        let msg = cosmrs::Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+            value: vec![],
+        };
+        let unsigned = builder::UnsignedTx {
+            body: Body::new(vec![msg], "memo", 0u32),
+            fee: cosmrs::proto::cosmos::tx::v1beta1::Fee::default(),
+            chain_id: "secretdev-1".to_owned(),
+            sequence: Sequence {
+                account_number: 7,
+                sequence: 3,
+            },
+            multisig,
+        };
+
+        let imported = builder::UnsignedTx::from_json(&unsigned.to_json().unwrap()).unwrap();
+        assert_eq!(imported.to_json().unwrap(), unsigned.to_json().unwrap());
 
-    pub fn init() -> Fee {
-        fee(consts::INIT_AMOUNT, consts::INIT_GAS)
+        let partial = imported.sign_partial(&b).unwrap();
+        assert_eq!(partial.member(), 1);
+        assert_eq!(
+            builder::PartialSignature::from_json(&partial.to_json()).unwrap(),
+            partial
+        );
+        assert_eq!(
+            unsigned.aggregate(&[partial]).unwrap(),
+            imported
+                .aggregate(&[imported.sign_partial(&b).unwrap()])
+                .unwrap()
+        );
+
+        let outsider = builder::PartialSignature::new(2, vec![0; 64]);
+        assert!(matches!(
+            unsigned.aggregate(&[outsider]),
+            Err(Error::Multisig(_))
+        ));
     }
 
-    pub fn exec() -> Fee {
-        fee(consts::EXEC_AMOUNT, consts::EXEC_GAS)
+    #[test]
+    fn signed_tx_json_roundtrip() {
+        use cosmrs::proto::cosmos::tx::v1beta1 as proto;
+
+        let body = proto::TxBody {
+            messages: vec![cosmrs::Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+                value: vec![],
+            }],
+            ..Default::default()
+        };
+        let tx_raw = proto::TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: proto::AuthInfo::default().encode_to_vec(),
+            signatures: vec![vec![1; 64]],
+        };
+        let signed = builder::SignedTx::from_bytes(tx_raw.encode_to_vec()).unwrap();
+        assert_eq!(
+            builder::SignedTx::from_json(&signed.to_json()).unwrap(),
+            signed
+        );
+        assert_eq!(
+            builder::SignedTx::from_base64(&signed.to_base64()).unwrap(),
+            signed
+        );
+
+        let tampered = signed.to_json().replace(signed.hash(), &"0".repeat(64));
+        assert!(builder::SignedTx::from_json(&tampered).is_err());
+
+        assert!(matches!(
+            builder::SignedTx::from_bytes(vec![1, 2, 3]),
+            Err(Error::InvalidSignedTx(_))
+        ));
+        let unsigned = proto::TxRaw {
+            signatures: vec![],
+            ..tx_raw
+        };
+        assert!(builder::SignedTx::from_bytes(unsigned.encode_to_vec()).is_err());
     }
 }
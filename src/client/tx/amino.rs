@@ -0,0 +1,562 @@
+use cosmrs::{
+    proto::cosmos::{
+        bank::v1beta1 as bank,
+        base::v1beta1::Coin,
+        crypto::multisig::v1beta1::CompactBitArray,
+        distribution::v1beta1 as distribution,
+        gov::v1beta1 as gov,
+        staking::v1beta1 as staking,
+        tx::{
+            signing::v1beta1::SignMode,
+            v1beta1::{mode_info, Fee, ModeInfo},
+        },
+    },
+    secret_cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgStoreCode},
+    tx::{Body, Msg},
+    Any,
+};
+use prost::Message;
+use serde_json::{json, Value};
+
+use crate::{Error, Result};
+
+/// The bytes signed in `SIGN_MODE_LEGACY_AMINO_JSON`: the `StdSignDoc` of the tx as canonical
+/// JSON.
+pub(super) fn sign_doc_bytes(
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    fee: &Fee,
+    body: &Body,
+) -> Result<Vec<u8>> {
+    let mut doc = json!({
+        "account_number": account_number.to_string(),
+        "chain_id": chain_id,
+        "fee": {
+            "amount": coins(&fee.amount),
+            "gas": fee.gas_limit.to_string(),
+        },
+        "memo": body.memo,
+        "msgs": body.messages.iter().map(msg).collect::<Result<Vec<_>>>()?,
+        "sequence": sequence.to_string(),
+    });
+
+    let timeout_height = body.timeout_height.value();
+    if timeout_height != 0 {
+        doc["timeout_height"] = json!(timeout_height.to_string());
+    }
+
+    Ok(canonical_json(doc).into_bytes())
+}
+
+pub(super) fn mode_info() -> ModeInfo {
+    ModeInfo {
+        sum: Some(mode_info::Sum::Single(mode_info::Single {
+            mode: SignMode::LegacyAminoJson as i32,
+        })),
+    }
+}
+
+/// The mode info of a multisig signed by the members at the sorted positions `signed`, each in
+/// legacy Amino JSON mode.
+pub(super) fn multisig_mode_info(members: usize, signed: &[usize]) -> ModeInfo {
+    let mut elems = vec![0u8; (members + 7) / 8];
+    for &member in signed {
+        elems[member / 8] |= 1 << (7 - member % 8);
+    }
+
+    ModeInfo {
+        sum: Some(mode_info::Sum::Multi(mode_info::Multi {
+            bitarray: Some(CompactBitArray {
+                extra_bits_stored: (members % 8) as u32,
+                elems,
+            }),
+            mode_infos: signed.iter().map(|_| mode_info()).collect(),
+        })),
+    }
+}
+
+/// Sorted keys without whitespace, with `<`, `>` and `&` escaped like Go's `encoding/json`
+/// does when the chain sorts the sign doc.
+fn canonical_json(value: Value) -> String {
+    sort_keys(value)
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+// serde_json only keeps maps sorted as long as no crate enables its `preserve_order` feature
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// The Amino JSON of a message, as registered with the legacy codec of its module.
+fn msg(any: &Any) -> Result<Value> {
+    let (amino_type, value) = match any.type_url.as_str() {
+        "/secret.compute.v1beta1.MsgStoreCode" => {
+            let msg = MsgStoreCode::from_any(any)?;
+            let mut value = json!({
+                "sender": msg.sender.to_string(),
+                "wasm_byte_code": base64::encode(&msg.wasm_byte_code),
+            });
+            // `omitempty` strings, unlike the non-nullable coin lists of the other compute msgs
+            for (field, text) in [("source", msg.source), ("builder", msg.builder)] {
+                if let Some(text) = text.filter(|text| !text.is_empty()) {
+                    value[field] = json!(text);
+                }
+            }
+            ("wasm/MsgStoreCode", value)
+        }
+        "/secret.compute.v1beta1.MsgInstantiateContract" => {
+            let msg = MsgInstantiateContract::from_any(any)?;
+            let value = json!({
+                "sender": msg.sender.to_string(),
+                "code_id": msg.code_id.to_string(),
+                "label": msg.label,
+                "init_msg": base64::encode(&msg.init_msg),
+                // non-nullable coins are never omitted, the empty callback fields are
+                "init_funds": [],
+            });
+            ("wasm/MsgInstantiateContract", value)
+        }
+        "/secret.compute.v1beta1.MsgExecuteContract" => {
+            let msg = MsgExecuteContract::from_any(any)?;
+            let sent_funds: Vec<_> = msg
+                .sent_funds
+                .iter()
+                .map(|coin| json!({"amount": coin.amount.to_string(), "denom": coin.denom.to_string()}))
+                .collect();
+            let value = json!({
+                "sender": msg.sender.to_string(),
+                "contract": msg.contract.to_string(),
+                "msg": base64::encode(&msg.msg),
+                "sent_funds": sent_funds,
+            });
+            ("wasm/MsgExecuteContract", value)
+        }
+        "/cosmos.bank.v1beta1.MsgSend" => {
+            let msg = decode::<bank::MsgSend>(any)?;
+            let value = json!({
+                "from_address": msg.from_address,
+                "to_address": msg.to_address,
+                "amount": coins(&msg.amount),
+            });
+            ("cosmos-sdk/MsgSend", value)
+        }
+        "/cosmos.bank.v1beta1.MsgMultiSend" => {
+            let msg = decode::<bank::MsgMultiSend>(any)?;
+            let io = |address: &str, amount: &[Coin]| json!({"address": address, "coins": coins(amount)});
+            let value = json!({
+                "inputs": msg.inputs.iter().map(|i| io(&i.address, &i.coins)).collect::<Vec<_>>(),
+                "outputs": msg.outputs.iter().map(|o| io(&o.address, &o.coins)).collect::<Vec<_>>(),
+            });
+            ("cosmos-sdk/MsgMultiSend", value)
+        }
+        "/cosmos.staking.v1beta1.MsgDelegate" => {
+            let msg = decode::<staking::MsgDelegate>(any)?;
+            let value = json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            });
+            ("cosmos-sdk/MsgDelegate", value)
+        }
+        "/cosmos.staking.v1beta1.MsgUndelegate" => {
+            let msg = decode::<staking::MsgUndelegate>(any)?;
+            let value = json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            });
+            ("cosmos-sdk/MsgUndelegate", value)
+        }
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => {
+            let msg = decode::<staking::MsgBeginRedelegate>(any)?;
+            let value = json!({
+                "delegator_address": msg.delegator_address,
+                "validator_src_address": msg.validator_src_address,
+                "validator_dst_address": msg.validator_dst_address,
+                "amount": msg.amount.as_ref().map(coin),
+            });
+            ("cosmos-sdk/MsgBeginRedelegate", value)
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => {
+            let msg = decode::<distribution::MsgWithdrawDelegatorReward>(any)?;
+            let value = json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+            });
+            ("cosmos-sdk/MsgWithdrawDelegationReward", value)
+        }
+        "/cosmos.gov.v1beta1.MsgDeposit" => {
+            let msg = decode::<gov::MsgDeposit>(any)?;
+            let value = json!({
+                "proposal_id": msg.proposal_id.to_string(),
+                "depositor": msg.depositor,
+                "amount": coins(&msg.amount),
+            });
+            ("cosmos-sdk/MsgDeposit", value)
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => {
+            let msg = decode::<gov::MsgVote>(any)?;
+            let value = json!({
+                "proposal_id": msg.proposal_id.to_string(),
+                "voter": msg.voter,
+                "option": msg.option,
+            });
+            ("cosmos-sdk/MsgVote", value)
+        }
+        type_url => return Err(Error::UnsupportedAminoMsg(type_url.to_owned())),
+    };
+
+    Ok(json!({ "type": amino_type, "value": value }))
+}
+
+fn decode<T: Message + Default>(any: &Any) -> Result<T> {
+    Ok(T::decode(any.value.as_slice())?)
+}
+
+fn coin(coin: &Coin) -> Value {
+    json!({ "amount": coin.amount, "denom": coin.denom })
+}
+
+fn coins(coins: &[Coin]) -> Vec<Value> {
+    coins.iter().map(coin).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn canonical_json_sorts_and_escapes() {
+        let value = json!({
+            "memo": "<a & b>",
+            "fee": { "gas": "1", "amount": [] },
+        });
+        assert_eq!(
+            canonical_json(value),
+            r#"{"fee":{"amount":[],"gas":"1"},"memo":"\u003ca \u0026 b\u003e"}"#
+        );
+    }
+
+    // the sign docs the chain's legacy codec builds: gogoproto `omitempty` drops empty strings and
+    // bytes, while non-nullable coin lists are always written, as `[]` when empty
+    const A: &str = "secret1qyqszqgpqyqszqgpqyqszqgpqyqszqgpsk4hsq";
+    const B: &str = "secret1qgpqyqszqgpqyqszqgpqyqszqgpqyqszpjnjmk";
+    const VAL_1: &str = "secretvaloper1qyqszqgpqyqszqgpqyqszqgpqyqszqgpp3m2af";
+    const VAL_2: &str = "secretvaloper1qgpqyqszqgpqyqszqgpqyqszqgpqyqszs4a0kl";
+
+    fn sign_doc(msg: Any, timeout_height: u32) -> String {
+        let fee = Fee {
+            amount: vec![uscrt(5000)],
+            gas_limit: 200_000,
+            payer: String::new(),
+            granter: String::new(),
+        };
+        let body = Body::new(vec![msg], "memo", timeout_height);
+        String::from_utf8(sign_doc_bytes("secretdev-1", 7, 3, &fee, &body).unwrap()).unwrap()
+    }
+
+    /// The `StdSignDoc` the chain signs for a tx with the single amino `msg`.
+    fn std_sign_doc(msg: &str) -> String {
+        format!(
+            r#"{{"account_number":"7","chain_id":"secretdev-1","fee":{{"amount":[{{"amount":"5000","denom":"uscrt"}}],"gas":"200000"}},"memo":"memo","msgs":[{msg}],"sequence":"3"}}"#
+        )
+    }
+
+    fn uscrt(amount: u64) -> Coin {
+        Coin {
+            denom: "uscrt".to_owned(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn any(type_url: &str, msg: impl Message) -> Any {
+        Any {
+            type_url: type_url.to_owned(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn sign_doc_timeout_height() {
+        let msg = any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            distribution::MsgWithdrawDelegatorReward {
+                delegator_address: A.to_owned(),
+                validator_address: VAL_1.to_owned(),
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 42),
+            format!(
+                r#"{{"account_number":"7","chain_id":"secretdev-1","fee":{{"amount":[{{"amount":"5000","denom":"uscrt"}}],"gas":"200000"}},"memo":"memo","msgs":[{{"type":"cosmos-sdk/MsgWithdrawDelegationReward","value":{{"delegator_address":"{A}","validator_address":"{VAL_1}"}}}}],"sequence":"3","timeout_height":"42"}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn sign_doc_store_code() {
+        let msg = MsgStoreCode {
+            sender: A.parse().unwrap(),
+            wasm_byte_code: b"\0asm".to_vec(),
+            source: None,
+            builder: None,
+        };
+        assert_eq!(
+            sign_doc(msg.to_any().unwrap(), 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"wasm/MsgStoreCode","value":{{"sender":"{A}","wasm_byte_code":"AGFzbQ=="}}}}"#
+            ))
+        );
+
+        let msg = MsgStoreCode {
+            sender: A.parse().unwrap(),
+            wasm_byte_code: b"\0asm".to_vec(),
+            source: Some("https://github.com/scrtlabs/secret-contracts".to_owned()),
+            builder: Some("enigmampc/secret-contract-optimizer:1.0.7".to_owned()),
+        };
+        assert_eq!(
+            sign_doc(msg.to_any().unwrap(), 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"wasm/MsgStoreCode","value":{{"builder":"enigmampc/secret-contract-optimizer:1.0.7","sender":"{A}","source":"https://github.com/scrtlabs/secret-contracts","wasm_byte_code":"AGFzbQ=="}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_instantiate_contract() {
+        let msg = MsgInstantiateContract {
+            sender: A.parse().unwrap(),
+            code_id: 1,
+            label: "greeter".to_owned(),
+            init_msg: br#"{"a":1}"#.to_vec(),
+        };
+        assert_eq!(
+            sign_doc(msg.to_any().unwrap(), 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"wasm/MsgInstantiateContract","value":{{"code_id":"1","init_funds":[],"init_msg":"eyJhIjoxfQ==","label":"greeter","sender":"{A}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_execute_contract() {
+        let msg = MsgExecuteContract {
+            sender: A.parse().unwrap(),
+            contract: B.parse().unwrap(),
+            msg: br#"{"a":1}"#.to_vec(),
+            sent_funds: vec![cosmrs::Coin {
+                denom: "uscrt".parse().unwrap(),
+                amount: 10u64.into(),
+            }],
+        };
+        assert_eq!(
+            sign_doc(msg.to_any().unwrap(), 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"wasm/MsgExecuteContract","value":{{"contract":"{B}","msg":"eyJhIjoxfQ==","sender":"{A}","sent_funds":[{{"amount":"10","denom":"uscrt"}}]}}}}"#
+            ))
+        );
+
+        let msg = MsgExecuteContract {
+            sent_funds: vec![],
+            ..msg
+        };
+        assert_eq!(
+            sign_doc(msg.to_any().unwrap(), 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"wasm/MsgExecuteContract","value":{{"contract":"{B}","msg":"eyJhIjoxfQ==","sender":"{A}","sent_funds":[]}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_send() {
+        let msg = any(
+            "/cosmos.bank.v1beta1.MsgSend",
+            bank::MsgSend {
+                from_address: A.to_owned(),
+                to_address: B.to_owned(),
+                amount: vec![uscrt(1000)],
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"uscrt"}}],"from_address":"{A}","to_address":"{B}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_multi_send() {
+        let msg = any(
+            "/cosmos.bank.v1beta1.MsgMultiSend",
+            bank::MsgMultiSend {
+                inputs: vec![bank::Input {
+                    address: A.to_owned(),
+                    coins: vec![uscrt(1000)],
+                }],
+                outputs: vec![bank::Output {
+                    address: B.to_owned(),
+                    coins: vec![uscrt(1000)],
+                }],
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgMultiSend","value":{{"inputs":[{{"address":"{A}","coins":[{{"amount":"1000","denom":"uscrt"}}]}}],"outputs":[{{"address":"{B}","coins":[{{"amount":"1000","denom":"uscrt"}}]}}]}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_delegate() {
+        let msg = any(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            staking::MsgDelegate {
+                delegator_address: A.to_owned(),
+                validator_address: VAL_1.to_owned(),
+                amount: Some(uscrt(1_000_000)),
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgDelegate","value":{{"amount":{{"amount":"1000000","denom":"uscrt"}},"delegator_address":"{A}","validator_address":"{VAL_1}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_undelegate() {
+        let msg = any(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            staking::MsgUndelegate {
+                delegator_address: A.to_owned(),
+                validator_address: VAL_1.to_owned(),
+                amount: Some(uscrt(500_000)),
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgUndelegate","value":{{"amount":{{"amount":"500000","denom":"uscrt"}},"delegator_address":"{A}","validator_address":"{VAL_1}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_begin_redelegate() {
+        let msg = any(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            staking::MsgBeginRedelegate {
+                delegator_address: A.to_owned(),
+                validator_src_address: VAL_1.to_owned(),
+                validator_dst_address: VAL_2.to_owned(),
+                amount: Some(uscrt(500_000)),
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgBeginRedelegate","value":{{"amount":{{"amount":"500000","denom":"uscrt"}},"delegator_address":"{A}","validator_dst_address":"{VAL_2}","validator_src_address":"{VAL_1}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_withdraw_delegator_reward() {
+        let msg = any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            distribution::MsgWithdrawDelegatorReward {
+                delegator_address: A.to_owned(),
+                validator_address: VAL_1.to_owned(),
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgWithdrawDelegationReward","value":{{"delegator_address":"{A}","validator_address":"{VAL_1}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_deposit() {
+        let msg = any(
+            "/cosmos.gov.v1beta1.MsgDeposit",
+            gov::MsgDeposit {
+                proposal_id: 1,
+                depositor: A.to_owned(),
+                amount: vec![uscrt(10_000_000)],
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgDeposit","value":{{"amount":[{{"amount":"10000000","denom":"uscrt"}}],"depositor":"{A}","proposal_id":"1"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_vote() {
+        let msg = any(
+            "/cosmos.gov.v1beta1.MsgVote",
+            gov::MsgVote {
+                proposal_id: 1,
+                voter: A.to_owned(),
+                option: gov::VoteOption::Yes as i32,
+            },
+        );
+        assert_eq!(
+            sign_doc(msg, 0),
+            std_sign_doc(&format!(
+                r#"{{"type":"cosmos-sdk/MsgVote","value":{{"option":1,"proposal_id":"1","voter":"{A}"}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sign_doc_unsupported_msg() {
+        let msg = Any {
+            type_url: "/cosmos.authz.v1beta1.MsgExec".to_owned(),
+            value: vec![],
+        };
+        let body = Body::new(vec![msg], "", 0u32);
+        assert!(matches!(
+            sign_doc_bytes("secretdev-1", 7, 3, &Fee::default(), &body),
+            Err(Error::UnsupportedAminoMsg(_))
+        ));
+    }
+
+    #[test]
+    fn multisig_bit_array() {
+        let bitarray = match multisig_mode_info(3, &[0, 2]).sum {
+            Some(mode_info::Sum::Multi(multi)) => {
+                assert_eq!(multi.mode_infos.len(), 2);
+                multi.bitarray.unwrap()
+            }
+            _ => panic!("expected multi mode info"),
+        };
+        assert_eq!(bitarray.extra_bits_stored, 3);
+        assert_eq!(bitarray.elems, vec![0b1010_0000]);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use cosmrs::{proto::cosmos::bank::v1beta1 as bank, AccountId};
+use cosmwasm_std::{Coin, HumanAddr};
+
+use crate::{Error, Result};
+
+use super::{any, proto_coin, Broadcast, PreparedTx, Signer, Tx, Unspecified};
+
+pub struct BankSend {
+    to: HumanAddr,
+    amount: Vec<Coin>,
+}
+
+pub struct MultiSend {
+    outputs: Vec<(HumanAddr, Vec<Coin>)>,
+}
+
+impl BankSend {
+    pub(super) fn to_any(&self, from: &AccountId) -> cosmrs::Any {
+        let msg = bank::MsgSend {
+            from_address: from.to_string(),
+            to_address: self.to.to_string(),
+            amount: self.amount.iter().cloned().map(proto_coin).collect(),
+        };
+        any("/cosmos.bank.v1beta1.MsgSend", msg)
+    }
+}
+
+impl MultiSend {
+    fn to_any(&self, from: &AccountId) -> Result<cosmrs::Any> {
+        let mut total = BTreeMap::<String, u128>::new();
+        for coin in self.outputs.iter().flat_map(|(_, coins)| coins) {
+            let sum = total.entry(coin.denom.clone()).or_default();
+            *sum = sum
+                .checked_add(coin.amount.u128())
+                .ok_or_else(|| Error::AmountOverflow(coin.denom.clone()))?;
+        }
+
+        let input = bank::Input {
+            address: from.to_string(),
+            coins: total
+                .into_iter()
+                .map(|(denom, amount)| proto_coin(cosmwasm_std::coin(amount, &denom)))
+                .collect(),
+        };
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|(to, coins)| bank::Output {
+                address: to.to_string(),
+                coins: coins.iter().cloned().map(proto_coin).collect(),
+            })
+            .collect();
+
+        let msg = bank::MsgMultiSend {
+            inputs: vec![input],
+            outputs,
+        };
+        Ok(any("/cosmos.bank.v1beta1.MsgMultiSend", msg))
+    }
+}
+
+impl<'a, From> Tx<'a, Unspecified, From> {
+    pub fn send(self, to: impl Into<HumanAddr>, amount: Vec<Coin>) -> Tx<'a, BankSend, From> {
+        self.with_kind(BankSend {
+            to: to.into(),
+            amount,
+        })
+    }
+
+    /// Sends to several recipients in one message, add recipients with [`Tx::to`].
+    pub fn multi_send(self) -> Tx<'a, MultiSend, From> {
+        self.with_kind(MultiSend { outputs: vec![] })
+    }
+}
+
+impl<'a, From> Tx<'a, MultiSend, From> {
+    pub fn to(mut self, recipient: impl Into<HumanAddr>, amount: Vec<Coin>) -> Self {
+        self.kind.outputs.push((recipient.into(), amount));
+        self
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, BankSend, F> {
+    type Response = ();
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let gas = fee.or_gas(crate::client::tx::gas::send());
+
+        let msg = kind.to_any(&from.id());
+        Ok(PreparedTx::single(msg, from, gas, vec![], |_| Ok(())))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, MultiSend, F> {
+    type Response = ();
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let gas = fee.or_gas(crate::client::tx::gas::send());
+
+        let msg = kind.to_any(&from.id())?;
+        Ok(PreparedTx::single(msg, from, gas, vec![], |_| Ok(())))
+    }
+}
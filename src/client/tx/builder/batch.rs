@@ -0,0 +1,246 @@
+use cosmrs::{
+    secret_cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgStoreCode},
+    tx::Msg,
+};
+
+use crate::{
+    client::types::ContractInit, consts, crypto::Decrypter, CodeHash, CodeId, Contract, Error,
+    Result,
+};
+
+use super::{
+    BankSend, Broadcast, Execute, Initialize, PreparedTx, Signer, Tx, Unspecified, Upload,
+};
+
+/// Several messages signed and broadcast as one atomic tx.
+///
+/// Init messages look up the code hash of their code id when the tx is built, so the code must
+/// already be stored: an upload and an init of the uploaded code cannot share a batch, building
+/// such a batch fails because the code id is not found yet.
+pub struct Batch {
+    msgs: Vec<BatchMsg>,
+}
+
+/// A message that can be added to a [`Batch`], built with the single message builders.
+pub struct BatchMsg(BatchMsgKind);
+
+enum BatchMsgKind {
+    Upload(Upload),
+    Init(Initialize<serde_json::Value>),
+    Execute(Execute<serde_json::Value, ()>),
+    Send(BankSend),
+}
+
+pub trait Batchable {
+    fn into_batch_msg(self) -> BatchMsg;
+}
+
+impl Batchable for Upload {
+    fn into_batch_msg(self) -> BatchMsg {
+        BatchMsg(BatchMsgKind::Upload(self))
+    }
+}
+
+impl<M: serde::Serialize> Batchable for Initialize<M> {
+    fn into_batch_msg(self) -> BatchMsg {
+        BatchMsg(BatchMsgKind::Init(Initialize {
+            msg: serde_json::to_value(&self.msg).expect("msg cannot be serialized as JSON"),
+            code_id: self.code_id,
+            label: self.label,
+        }))
+    }
+}
+
+impl<M: serde::Serialize, R> Batchable for Execute<M, R> {
+    fn into_batch_msg(self) -> BatchMsg {
+        BatchMsg(BatchMsgKind::Execute(Execute {
+            msg: serde_json::to_value(&self.msg).expect("msg cannot be serialized as JSON"),
+            contract: self.contract,
+            sent_funds: self.sent_funds,
+            _response: std::marker::PhantomData,
+        }))
+    }
+}
+
+impl Batchable for BankSend {
+    fn into_batch_msg(self) -> BatchMsg {
+        BatchMsg(BatchMsgKind::Send(self))
+    }
+}
+
+impl BatchMsg {
+    fn default_gas(&self) -> u64 {
+        match &self.0 {
+            BatchMsgKind::Upload(_) => consts::UPLOAD_GAS,
+            BatchMsgKind::Init(_) => consts::INIT_GAS,
+            BatchMsgKind::Execute(_) => consts::EXEC_GAS,
+            BatchMsgKind::Send(_) => consts::SEND_GAS,
+        }
+    }
+}
+
+/// The response to one message of a batch, in the order the messages were added.
+#[derive(Debug, Clone)]
+pub enum MsgResponse {
+    Upload(CodeId),
+    Init(Contract),
+    /// The decrypted data returned by the contract.
+    Execute(Vec<u8>),
+    Send,
+}
+
+impl MsgResponse {
+    pub fn code_id(&self) -> Option<CodeId> {
+        match self {
+            MsgResponse::Upload(code_id) => Some(*code_id),
+            _ => None,
+        }
+    }
+
+    pub fn contract(&self) -> Option<&Contract> {
+        match self {
+            MsgResponse::Init(contract) => Some(contract),
+            _ => None,
+        }
+    }
+
+    /// Deserializes the JSON response of an execute message, `None` for other messages.
+    pub fn execute_response<R: serde::de::DeserializeOwned>(&self) -> Result<Option<R>> {
+        match self {
+            MsgResponse::Execute(buf) => Ok(Some(serde_json::from_slice(buf)?)),
+            _ => Ok(None),
+        }
+    }
+}
+
+enum PendingResponse {
+    Upload,
+    Init(CodeHash),
+    Execute(Decrypter),
+    Send,
+}
+
+impl PendingResponse {
+    fn into_response(self, data: Vec<u8>) -> Result<MsgResponse> {
+        match self {
+            PendingResponse::Upload => Ok(MsgResponse::Upload(CodeId::try_from(data)?)),
+            PendingResponse::Init(code_hash) => {
+                let init = ContractInit::try_from(data)?;
+                Ok(MsgResponse::Init(init.into_contract(code_hash)))
+            }
+            PendingResponse::Execute(_) if data.is_empty() => Ok(MsgResponse::Execute(vec![])),
+            PendingResponse::Execute(decrypter) => {
+                let plaintext = decrypter.decrypt(&data)?;
+                let b64 = String::from_utf8(plaintext)?;
+                Ok(MsgResponse::Execute(base64::decode(b64)?))
+            }
+            PendingResponse::Send => Ok(MsgResponse::Send),
+        }
+    }
+}
+
+impl<'a, From> Tx<'a, Unspecified, From> {
+    pub fn batch(self) -> Tx<'a, Batch, From> {
+        self.with_kind(Batch { msgs: vec![] })
+    }
+}
+
+impl<'a, From> Tx<'a, Batch, From> {
+    /// Adds the message of a single message tx, e.g. `client.tx().execute(&msg, &contract)`.
+    pub fn add<K: Batchable>(mut self, tx: Tx<'_, K, Unspecified>) -> Self {
+        self.kind.msgs.push(tx.kind.into_batch_msg());
+        self
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Batch, F> {
+    type Response = Vec<MsgResponse>;
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            client,
+            kind,
+            from,
+            fee,
+            ..
+        } = self;
+        let from: Signer = from.into();
+        let encryption_account = from.encryption_account();
+
+        let gas = kind.msgs.iter().map(BatchMsg::default_gas).sum();
+        let fee = fee.or_gas(crate::client::tx::Gas::Limit(gas));
+
+        let mut msgs = Vec::with_capacity(kind.msgs.len());
+        let mut pending = Vec::with_capacity(kind.msgs.len());
+        let mut decrypters = vec![];
+
+        for msg in kind.msgs {
+            match msg.0 {
+                BatchMsgKind::Upload(upload) => {
+                    let wasm_byte_code = std::fs::read(&upload.path).map_err(|err| {
+                        Error::ContractFile(format!("{}", upload.path.display()), err)
+                    })?;
+                    let msg = MsgStoreCode {
+                        sender: from.id(),
+                        wasm_byte_code,
+                        source: None,
+                        builder: None,
+                    };
+                    msgs.push(msg.to_any()?);
+                    pending.push(PendingResponse::Upload);
+                }
+                BatchMsgKind::Init(init) => {
+                    let label = init.label();
+                    if client.query_contract_label_exists(&label)? {
+                        return Err(Error::ContractLabelExists(label));
+                    }
+                    let code_hash = client.query_code_hash_by_code_id(init.code_id)?;
+                    let (_, encrypted_msg) =
+                        client.encrypt_msg(&init.msg, &code_hash, encryption_account)?;
+                    let msg = MsgInstantiateContract {
+                        sender: from.id(),
+                        code_id: init.code_id.into(),
+                        label,
+                        init_msg: encrypted_msg,
+                    };
+                    msgs.push(msg.to_any()?);
+                    pending.push(PendingResponse::Init(code_hash));
+                }
+                BatchMsgKind::Execute(exec) => {
+                    let (nonce, encrypted_msg) = client.encrypt_msg(
+                        &exec.msg,
+                        exec.contract.code_hash(),
+                        encryption_account,
+                    )?;
+                    let msg = MsgExecuteContract {
+                        sender: from.id(),
+                        contract: exec.contract.id(),
+                        msg: encrypted_msg,
+                        sent_funds: exec.sent_funds,
+                    };
+                    let decrypter = client.decrypter(&nonce, encryption_account)?;
+                    msgs.push(msg.to_any()?);
+                    decrypters.push(decrypter);
+                    pending.push(PendingResponse::Execute(decrypter));
+                }
+                BatchMsgKind::Send(send) => {
+                    msgs.push(send.to_any(&from.id()));
+                    pending.push(PendingResponse::Send);
+                }
+            }
+        }
+
+        Ok(PreparedTx::new(msgs, from, fee, decrypters, move |tx| {
+            tx.try_map(|msg_data| {
+                if msg_data.len() != pending.len() {
+                    return Err(Error::BatchResponseMismatch(pending.len(), msg_data.len()));
+                }
+                pending
+                    .into_iter()
+                    .zip(msg_data)
+                    .map(|(pending, msg_data)| pending.into_response(msg_data.data))
+                    .collect::<Result<Vec<_>>>()
+            })
+        }))
+    }
+}
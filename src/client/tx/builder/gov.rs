@@ -0,0 +1,220 @@
+use cosmrs::proto::cosmos::{base::v1beta1::Coin as ProtoCoin, gov::v1beta1 as gov};
+use prost::Message;
+
+use crate::{consts, Result};
+
+use super::{any, proto_coin, Broadcast, PreparedTx, Signer, Tx, Unspecified};
+
+#[derive(Debug, Clone)]
+pub enum ProposalContent {
+    Text {
+        title: String,
+        description: String,
+    },
+    ParameterChange {
+        title: String,
+        description: String,
+        changes: Vec<ParamChange>,
+    },
+    CommunityPoolSpend {
+        title: String,
+        description: String,
+        recipient: cosmwasm_std::HumanAddr,
+        amount: Vec<cosmwasm_std::Coin>,
+    },
+}
+
+/// A single parameter update. `value` is the JSON encoding of the new parameter value.
+#[derive(Debug, Clone)]
+pub struct ParamChange {
+    pub subspace: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl ProposalContent {
+    fn to_any(&self) -> cosmrs::Any {
+        match self.clone() {
+            ProposalContent::Text { title, description } => any(
+                "/cosmos.gov.v1beta1.TextProposal",
+                gov::TextProposal { title, description },
+            ),
+            ProposalContent::ParameterChange {
+                title,
+                description,
+                changes,
+            } => {
+                use cosmrs::proto::cosmos::params::v1beta1 as params;
+                let changes = changes
+                    .into_iter()
+                    .map(|c| params::ParamChange {
+                        subspace: c.subspace,
+                        key: c.key,
+                        value: c.value,
+                    })
+                    .collect();
+                any(
+                    "/cosmos.params.v1beta1.ParameterChangeProposal",
+                    params::ParameterChangeProposal {
+                        title,
+                        description,
+                        changes,
+                    },
+                )
+            }
+            ProposalContent::CommunityPoolSpend {
+                title,
+                description,
+                recipient,
+                amount,
+            } => {
+                use cosmrs::proto::cosmos::distribution::v1beta1::CommunityPoolSpendProposal;
+                any(
+                    "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal",
+                    CommunityPoolSpendProposal {
+                        title,
+                        description,
+                        recipient: recipient.to_string(),
+                        amount: amount.into_iter().map(proto_coin).collect(),
+                    },
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteOption {
+    Yes = 1,
+    Abstain = 2,
+    No = 3,
+    NoWithVeto = 4,
+}
+
+pub struct SubmitProposal {
+    content: ProposalContent,
+    initial_deposit: u64,
+}
+
+pub struct Deposit {
+    proposal_id: u64,
+    amount: u64,
+}
+
+pub struct Vote {
+    proposal_id: u64,
+    option: VoteOption,
+}
+
+impl<'a, From> Tx<'a, Unspecified, From> {
+    pub fn submit_proposal(self, content: ProposalContent) -> Tx<'a, SubmitProposal, From> {
+        self.with_kind(SubmitProposal {
+            content,
+            initial_deposit: 0,
+        })
+    }
+
+    pub fn deposit(self, proposal_id: u64, amount: u64) -> Tx<'a, Deposit, From> {
+        self.with_kind(Deposit {
+            proposal_id,
+            amount,
+        })
+    }
+
+    pub fn vote(self, proposal_id: u64, option: VoteOption) -> Tx<'a, Vote, From> {
+        self.with_kind(Vote {
+            proposal_id,
+            option,
+        })
+    }
+}
+
+impl<'a, From> Tx<'a, SubmitProposal, From> {
+    /// The uscrt deposited together with the proposal.
+    pub fn initial_deposit(mut self, amount: u64) -> Self {
+        self.kind.initial_deposit = amount;
+        self
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, SubmitProposal, F> {
+    /// The id of the submitted proposal.
+    type Response = u64;
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let initial_deposit = match kind.initial_deposit {
+            0 => vec![],
+            amount => vec![uscrt(amount)],
+        };
+
+        let msg = gov::MsgSubmitProposal {
+            content: Some(kind.content.to_any()),
+            initial_deposit,
+            proposer: from.id().to_string(),
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::gov());
+
+        let msg = any("/cosmos.gov.v1beta1.MsgSubmitProposal", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |data| {
+            let res = gov::MsgSubmitProposalResponse::decode(data.as_slice())?;
+            Ok(res.proposal_id)
+        }))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Deposit, F> {
+    type Response = ();
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = gov::MsgDeposit {
+            proposal_id: kind.proposal_id,
+            depositor: from.id().to_string(),
+            amount: vec![uscrt(kind.amount)],
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::gov());
+
+        let msg = any("/cosmos.gov.v1beta1.MsgDeposit", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |_| Ok(())))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Vote, F> {
+    type Response = ();
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = gov::MsgVote {
+            proposal_id: kind.proposal_id,
+            voter: from.id().to_string(),
+            option: kind.option as i32,
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::gov());
+
+        let msg = any("/cosmos.gov.v1beta1.MsgVote", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |_| Ok(())))
+    }
+}
+
+fn uscrt(amount: u64) -> ProtoCoin {
+    ProtoCoin {
+        denom: consts::COIN_DENOM.to_owned(),
+        amount: amount.to_string(),
+    }
+}
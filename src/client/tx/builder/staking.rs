@@ -0,0 +1,179 @@
+use std::time::SystemTime;
+
+use cosmrs::proto::cosmos::{distribution::v1beta1 as distribution, staking::v1beta1 as staking};
+use prost::Message;
+
+use crate::{
+    client::{query::system_time, types::parse_coins},
+    consts, Result,
+};
+
+use super::{any, proto_coin, Broadcast, PreparedTx, Signer, Tx, Unspecified};
+
+pub struct Delegate {
+    validator: String,
+    amount: u64,
+}
+
+pub struct Undelegate {
+    validator: String,
+    amount: u64,
+}
+
+pub struct Redelegate {
+    src_validator: String,
+    dst_validator: String,
+    amount: u64,
+}
+
+pub struct WithdrawRewards {
+    validator: String,
+}
+
+impl<'a, From> Tx<'a, Unspecified, From> {
+    /// Delegates `amount` uscrt to the validator with operator address `validator`.
+    pub fn delegate(self, validator: impl Into<String>, amount: u64) -> Tx<'a, Delegate, From> {
+        self.with_kind(Delegate {
+            validator: validator.into(),
+            amount,
+        })
+    }
+
+    pub fn undelegate(self, validator: impl Into<String>, amount: u64) -> Tx<'a, Undelegate, From> {
+        self.with_kind(Undelegate {
+            validator: validator.into(),
+            amount,
+        })
+    }
+
+    pub fn redelegate(
+        self,
+        src_validator: impl Into<String>,
+        dst_validator: impl Into<String>,
+        amount: u64,
+    ) -> Tx<'a, Redelegate, From> {
+        self.with_kind(Redelegate {
+            src_validator: src_validator.into(),
+            dst_validator: dst_validator.into(),
+            amount,
+        })
+    }
+
+    pub fn withdraw_rewards(self, validator: impl Into<String>) -> Tx<'a, WithdrawRewards, From> {
+        self.with_kind(WithdrawRewards {
+            validator: validator.into(),
+        })
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Delegate, F> {
+    type Response = ();
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = staking::MsgDelegate {
+            delegator_address: from.id().to_string(),
+            validator_address: kind.validator,
+            amount: Some(uscrt(kind.amount)),
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::staking());
+
+        let msg = any("/cosmos.staking.v1beta1.MsgDelegate", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |_| Ok(())))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Undelegate, F> {
+    /// The time the undelegated tokens become available.
+    type Response = SystemTime;
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = staking::MsgUndelegate {
+            delegator_address: from.id().to_string(),
+            validator_address: kind.validator,
+            amount: Some(uscrt(kind.amount)),
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::staking());
+
+        let msg = any("/cosmos.staking.v1beta1.MsgUndelegate", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |data| {
+            let res = staking::MsgUndelegateResponse::decode(data.as_slice())?;
+            Ok(res.completion_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+                system_time(ts.seconds, ts.nanos)
+            }))
+        }))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, Redelegate, F> {
+    /// The time the redelegation completes.
+    type Response = SystemTime;
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = staking::MsgBeginRedelegate {
+            delegator_address: from.id().to_string(),
+            validator_src_address: kind.src_validator,
+            validator_dst_address: kind.dst_validator,
+            amount: Some(uscrt(kind.amount)),
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::staking());
+
+        let msg = any("/cosmos.staking.v1beta1.MsgBeginRedelegate", msg);
+        Ok(PreparedTx::single(msg, from, gas, vec![], |data| {
+            let res = staking::MsgBeginRedelegateResponse::decode(data.as_slice())?;
+            Ok(res.completion_time.map_or(SystemTime::UNIX_EPOCH, |ts| {
+                system_time(ts.seconds, ts.nanos)
+            }))
+        }))
+    }
+}
+
+impl<'a, F: Into<Signer>> Broadcast for Tx<'a, WithdrawRewards, F> {
+    /// The rewards paid out, empty if there were none.
+    type Response = Vec<cosmwasm_std::Coin>;
+
+    fn prepare(self) -> Result<PreparedTx<Self::Response>> {
+        let Tx {
+            kind, from, fee, ..
+        } = self;
+        let from: Signer = from.into();
+
+        let msg = distribution::MsgWithdrawDelegatorReward {
+            delegator_address: from.id().to_string(),
+            validator_address: kind.validator,
+        };
+
+        let gas = fee.or_gas(crate::client::tx::gas::staking());
+
+        let msg = any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            msg,
+        );
+        // the response message is empty in this SDK version, the amount is only in the events
+        Ok(PreparedTx::new(vec![msg], from, gas, vec![], |tx| {
+            let rewards = parse_coins(tx.event_attr("withdraw_rewards", "amount").unwrap_or(""))?;
+            Ok(tx.map(|_| rewards))
+        }))
+    }
+}
+
+fn uscrt(amount: u64) -> cosmrs::proto::cosmos::base::v1beta1::Coin {
+    proto_coin(cosmwasm_std::coin(amount.into(), consts::COIN_DENOM))
+}
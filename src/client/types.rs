@@ -10,7 +10,7 @@ pub enum ParseError {
     ContractInit(#[from] ParseContractInitError),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CodeId(u64);
 
 impl std::fmt::Display for CodeId {
@@ -19,6 +19,12 @@ impl std::fmt::Display for CodeId {
     }
 }
 
+impl From<u64> for CodeId {
+    fn from(code_id: u64) -> Self {
+        CodeId(code_id)
+    }
+}
+
 impl From<CodeId> for u64 {
     fn from(ci: CodeId) -> Self {
         ci.0
@@ -51,10 +57,7 @@ pub(crate) struct ContractInit(AccountId);
 
 impl ContractInit {
     pub fn into_contract(self, code_hash: CodeHash) -> Contract {
-        Contract {
-            id: self.0,
-            code_hash,
-        }
+        Contract::new(self.0, code_hash)
     }
 }
 
@@ -83,6 +86,10 @@ pub struct Contract {
 }
 
 impl Contract {
+    pub(crate) fn new(id: AccountId, code_hash: CodeHash) -> Contract {
+        Contract { id, code_hash }
+    }
+
     pub fn human_address(&self) -> cosmwasm_std::HumanAddr {
         self.id.to_string().into()
     }
@@ -100,6 +107,74 @@ impl Contract {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CodeInfo {
+    pub code_id: CodeId,
+    pub creator: cosmwasm_std::HumanAddr,
+    pub code_hash: CodeHash,
+    pub source: String,
+    pub builder: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContractInfo {
+    pub contract: Contract,
+    pub code_id: CodeId,
+    pub creator: cosmwasm_std::HumanAddr,
+    pub label: String,
+}
+
+/// Selects a window of a listing. The compute module returns listings in full, so the window is
+/// applied by the client.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pagination {
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl Pagination {
+    pub fn all() -> Self {
+        Pagination::default()
+    }
+
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Pagination {
+            offset,
+            limit: Some(limit),
+        }
+    }
+
+    pub(crate) fn apply<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        let items = items
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        Page { items, total }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The number of items available across all pages.
+    pub total: usize,
+}
+
+impl<T> Page<T> {
+    pub(crate) fn try_map<U>(self, f: impl FnMut(T) -> crate::Result<U>) -> crate::Result<Page<U>> {
+        Ok(Page {
+            items: self
+                .items
+                .into_iter()
+                .map(f)
+                .collect::<crate::Result<_>>()?,
+            total: self.total,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CodeHash(Vec<u8>);
 
@@ -130,26 +205,125 @@ impl std::fmt::Display for CodeHash {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Event {
     pub(crate) _type: String,
     pub(crate) attrs: HashMap<String, String>,
 }
 
+impl From<cosmrs::tendermint::abci::Event> for Event {
+    fn from(e: cosmrs::tendermint::abci::Event) -> Self {
+        let attrs = e
+            .attributes
+            .into_iter()
+            .map(|a| (a.key.to_string(), a.value.to_string()))
+            .collect();
+        Event {
+            _type: e.type_str,
+            attrs,
+        }
+    }
+}
+
+impl From<cosmrs::proto::tendermint::abci::Event> for Event {
+    fn from(e: cosmrs::proto::tendermint::abci::Event) -> Self {
+        let attrs = e
+            .attributes
+            .into_iter()
+            .map(|a| {
+                (
+                    String::from_utf8_lossy(&a.key).into_owned(),
+                    String::from_utf8_lossy(&a.value).into_owned(),
+                )
+            })
+            .collect();
+        Event {
+            _type: e.r#type,
+            attrs,
+        }
+    }
+}
+
+fn find_event_attr<'a>(events: &'a [Event], event_type: &str, attr: &str) -> Option<&'a str> {
+    events
+        .iter()
+        .find(|e| e._type == event_type)
+        .and_then(|e| e.attrs.get(attr))
+        .map(String::as_str)
+}
+
+/// A balance change reported by the `coin_spent` and `coin_received` events of a tx,
+/// including fee payments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalanceChange {
+    Spent {
+        address: cosmwasm_std::HumanAddr,
+        amount: Vec<cosmwasm_std::Coin>,
+    },
+    Received {
+        address: cosmwasm_std::HumanAddr,
+        amount: Vec<cosmwasm_std::Coin>,
+    },
+}
+
+fn find_balance_changes(events: &[Event]) -> crate::Result<Vec<BalanceChange>> {
+    events
+        .iter()
+        .filter_map(|e| match e._type.as_str() {
+            "coin_spent" => Some((e, "spender", true)),
+            "coin_received" => Some((e, "receiver", false)),
+            _ => None,
+        })
+        .map(|(e, address_attr, spent)| {
+            let address = e
+                .attrs
+                .get(address_attr)
+                .cloned()
+                .unwrap_or_default()
+                .into();
+            let amount = parse_coins(e.attrs.get("amount").map_or("", String::as_str))?;
+            Ok(if spent {
+                BalanceChange::Spent { address, amount }
+            } else {
+                BalanceChange::Received { address, amount }
+            })
+        })
+        .collect()
+}
+
+/// Parses the `100uscrt,5ibc/...` coin list format used in events.
+pub(crate) fn parse_coins(s: &str) -> crate::Result<Vec<cosmwasm_std::Coin>> {
+    s.split(',')
+        .filter(|coin| !coin.is_empty())
+        .map(|coin| {
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(coin.len());
+            let (amount, denom) = coin.split_at(split);
+            Ok(cosmwasm_std::Coin {
+                amount: amount.try_into()?,
+                denom: denom.to_owned(),
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct TxResponse<T> {
     pub response: Option<T>,
+    pub hash: String,
+    pub height: u64,
     pub gas_used: u64,
     pub(crate) events: Vec<Event>,
 }
 
 impl<T> TxResponse<T> {
     pub fn event_attr(&self, event_type: &str, attr: &str) -> Option<&str> {
-        self.events
-            .iter()
-            .find(|e| e._type == event_type)
-            .and_then(|e| e.attrs.get(attr))
-            .map(String::as_str)
+        find_event_attr(&self.events, event_type, attr)
+    }
+
+    pub fn balance_changes(&self) -> crate::Result<Vec<BalanceChange>> {
+        find_balance_changes(&self.events)
     }
 
     /// panics if the response is `None`
@@ -160,6 +334,8 @@ impl<T> TxResponse<T> {
     pub(crate) fn map<U, F: FnOnce(T) -> U>(self, f: F) -> TxResponse<U> {
         TxResponse {
             response: self.response.map(f),
+            hash: self.hash,
+            height: self.height,
             gas_used: self.gas_used,
             events: self.events,
         }
@@ -173,23 +349,429 @@ impl<T> TxResponse<T> {
         let response = self.response.map(|t| f(t)).transpose()?;
         Ok(TxResponse {
             response,
+            hash: self.hash,
+            height: self.height,
             gas_used: self.gas_used,
             events: self.events,
         })
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct AccountInfo {
+/// A transaction as committed on chain, fetched by hash or found by an event search.
+#[derive(Debug, Clone)]
+pub struct TxInfo {
+    pub hash: String,
+    pub height: u64,
+    /// The ABCI result code, `0` on success.
+    pub code: u32,
+    pub log: String,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub memo: String,
+    pub messages: Vec<cosmrs::Any>,
+    pub(crate) events: Vec<Event>,
+}
+
+impl TxInfo {
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+
+    pub fn event_attr(&self, event_type: &str, attr: &str) -> Option<&str> {
+        find_event_attr(&self.events, event_type, attr)
+    }
+
+    pub fn balance_changes(&self) -> crate::Result<Vec<BalanceChange>> {
+        find_balance_changes(&self.events)
+    }
+
+    /// Decodes every message of type `M`, skipping messages of other types.
+    pub fn decode_messages<M: cosmrs::tx::Msg>(&self) -> crate::Result<Vec<M>> {
+        use cosmrs::tx::MsgProto;
+        self.messages
+            .iter()
+            .filter(|any| any.type_url == M::Proto::TYPE_URL)
+            .map(|any| M::from_any(any).map_err(crate::Error::from))
+            .collect()
+    }
+
+    pub(crate) fn try_from_rpc(res: cosmrs::rpc::endpoint::tx::Response) -> crate::Result<Self> {
+        let tx = cosmrs::Tx::from_bytes(res.tx.as_bytes())?;
+        Ok(TxInfo {
+            hash: res.hash.to_string(),
+            height: res.height.value(),
+            code: res.tx_result.code.value(),
+            log: res.tx_result.log.to_string(),
+            gas_wanted: res.tx_result.gas_wanted.into(),
+            gas_used: res.tx_result.gas_used.into(),
+            memo: tx.body.memo,
+            messages: tx.body.messages,
+            events: res.tx_result.events.into_iter().map(Event::from).collect(),
+        })
+    }
+}
+
+/// Runs queries against the state at a past block height, see [`crate::Client::at_height`].
+pub struct AtHeight<'a> {
+    pub(crate) client: &'a crate::Client,
+    pub(crate) height: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeInfo {
+    pub chain_id: String,
+    pub moniker: String,
+    /// The Tendermint version of the node.
+    pub node_version: String,
+    pub app_name: String,
+    pub app_version: String,
+    pub latest_block_height: u64,
+    pub catching_up: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockInfo {
+    pub height: u64,
+    pub hash: String,
+    pub time: std::time::SystemTime,
+    pub chain_id: String,
+    pub num_txs: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondStatus {
+    Unspecified,
+    Unbonded,
+    Unbonding,
+    Bonded,
+}
+
+impl From<i32> for BondStatus {
+    fn from(status: i32) -> Self {
+        match status {
+            1 => BondStatus::Unbonded,
+            2 => BondStatus::Unbonding,
+            3 => BondStatus::Bonded,
+            _ => BondStatus::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    pub operator_address: String,
+    pub moniker: String,
+    pub jailed: bool,
+    pub status: BondStatus,
+    pub tokens: cosmwasm_std::Uint128,
+    pub delegator_shares: cosmwasm_std::Decimal,
+    pub commission_rate: cosmwasm_std::Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Delegation {
+    pub delegator: cosmwasm_std::HumanAddr,
+    pub validator: String,
+    pub shares: cosmwasm_std::Decimal,
+    pub balance: cosmwasm_std::Coin,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnbondingDelegation {
+    pub delegator: cosmwasm_std::HumanAddr,
+    pub validator: String,
+    pub entries: Vec<UnbondingEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnbondingEntry {
+    pub creation_height: u64,
+    pub completion_time: std::time::SystemTime,
+    pub initial_balance: cosmwasm_std::Uint128,
+    pub balance: cosmwasm_std::Uint128,
+}
+
+#[derive(Debug, Clone)]
+pub struct Redelegation {
+    pub delegator: cosmwasm_std::HumanAddr,
+    pub src_validator: String,
+    pub dst_validator: String,
+    pub entries: Vec<RedelegationEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RedelegationEntry {
+    pub creation_height: u64,
+    pub completion_time: std::time::SystemTime,
+    pub initial_balance: cosmwasm_std::Uint128,
+    pub shares_dst: cosmwasm_std::Decimal,
+    pub balance: cosmwasm_std::Uint128,
+}
+
+/// A coin with a fractional amount, as used for staking rewards and the community pool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecCoin {
+    pub denom: String,
+    pub amount: cosmwasm_std::Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct DelegatorRewards {
+    pub rewards: Vec<ValidatorRewards>,
+    pub total: Vec<DecCoin>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorRewards {
+    pub validator: String,
+    pub reward: Vec<DecCoin>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Unspecified,
+    DepositPeriod,
+    VotingPeriod,
+    Passed,
+    Rejected,
+    Failed,
+}
+
+impl From<i32> for ProposalStatus {
+    fn from(status: i32) -> Self {
+        match status {
+            1 => ProposalStatus::DepositPeriod,
+            2 => ProposalStatus::VotingPeriod,
+            3 => ProposalStatus::Passed,
+            4 => ProposalStatus::Rejected,
+            5 => ProposalStatus::Failed,
+            _ => ProposalStatus::Unspecified,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub id: u64,
+    pub status: ProposalStatus,
+    /// Only populated once voting has ended.
+    pub final_tally: TallyResult,
+    pub total_deposit: Vec<cosmwasm_std::Coin>,
+    pub submit_time: std::time::SystemTime,
+    pub deposit_end_time: std::time::SystemTime,
+    pub voting_start_time: std::time::SystemTime,
+    pub voting_end_time: std::time::SystemTime,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TallyResult {
+    pub yes: cosmwasm_std::Uint128,
+    pub abstain: cosmwasm_std::Uint128,
+    pub no: cosmwasm_std::Uint128,
+    pub no_with_veto: cosmwasm_std::Uint128,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountInfo {
+    pub address: cosmwasm_std::HumanAddr,
     pub account_number: u64,
     pub sequence_number: cosmrs::tx::SequenceNumber,
+    /// `None` until the account has signed a transaction, or if it uses a key type other than
+    /// secp256k1/ed25519.
+    pub public_key: Option<cosmrs::crypto::PublicKey>,
+    pub kind: AccountKind,
 }
 
-impl From<cosmrs::proto::cosmos::auth::v1beta1::BaseAccount> for AccountInfo {
-    fn from(ba: cosmrs::proto::cosmos::auth::v1beta1::BaseAccount) -> Self {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountKind {
+    Base,
+    Module {
+        name: String,
+        permissions: Vec<String>,
+    },
+    ContinuousVesting,
+    DelayedVesting,
+    PeriodicVesting,
+    PermanentLocked,
+}
+
+impl AccountInfo {
+    pub(crate) fn new(
+        ba: cosmrs::proto::cosmos::auth::v1beta1::BaseAccount,
+        kind: AccountKind,
+    ) -> Self {
         AccountInfo {
+            address: ba.address.into(),
             account_number: ba.account_number,
             sequence_number: ba.sequence,
+            public_key: ba
+                .pub_key
+                .and_then(|any| cosmrs::crypto::PublicKey::try_from(any).ok()),
+            kind,
         }
     }
 }
+
+/// The price of one unit of gas, e.g. `0.25uscrt`. Fees are `ceil(gas × price)` in its denom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasPrice {
+    /// The price scaled by 10^18, like `sdk.Dec`.
+    atomics: u128,
+    denom: String,
+}
+
+const GAS_PRICE_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+impl GasPrice {
+    pub fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    pub(crate) fn fee_amount(&self, gas: u64) -> crate::Result<u64> {
+        let overflow = || crate::Error::FeeOverflow(gas, self.to_string());
+        let scaled = (gas as u128)
+            .checked_mul(self.atomics)
+            .and_then(|scaled| scaled.checked_add(GAS_PRICE_FRACTIONAL - 1))
+            .ok_or_else(overflow)?;
+        u64::try_from(scaled / GAS_PRICE_FRACTIONAL).map_err(|_| overflow())
+    }
+}
+
+impl std::str::FromStr for GasPrice {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let invalid = || crate::Error::InvalidGasPrice(s.to_owned());
+
+        let split = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+        let (amount, denom) = s.split_at(split);
+        if !is_valid_denom(denom) {
+            return Err(invalid());
+        }
+        let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+        if int.is_empty() || frac.len() > 18 || frac.contains('.') {
+            return Err(invalid());
+        }
+
+        let int: u128 = int.parse().map_err(|_| invalid())?;
+        let frac: u128 = format!("{:0<18}", frac).parse().map_err(|_| invalid())?;
+        let atomics = int
+            .checked_mul(GAS_PRICE_FRACTIONAL)
+            .and_then(|int| int.checked_add(frac))
+            .ok_or_else(invalid)?;
+
+        Ok(GasPrice {
+            atomics,
+            denom: denom.to_owned(),
+        })
+    }
+}
+
+/// A coin denom as the SDK accepts it: `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`.
+fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+    (3..=128).contains(&denom.len())
+        && chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+impl std::fmt::Display for GasPrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let int = self.atomics / GAS_PRICE_FRACTIONAL;
+        let frac = format!("{:018}", self.atomics % GAS_PRICE_FRACTIONAL);
+        let frac = frac.trim_end_matches('0');
+        if frac.is_empty() {
+            write!(f, "{}{}", int, self.denom)
+        } else {
+            write!(f, "{}.{}{}", int, frac, self.denom)
+        }
+    }
+}
+
+/// Named uscrt gas prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeProfile {
+    /// The minimum gas price accepted by localsecret.
+    Cheap,
+    /// The gas price the client starts with.
+    Default,
+    /// Twice the default, for txs that must not be rejected for low fees.
+    Generous,
+}
+
+impl FeeProfile {
+    pub fn gas_price(self) -> GasPrice {
+        let price = match self {
+            FeeProfile::Cheap => crate::consts::CHEAP_GAS_PRICE,
+            FeeProfile::Default => crate::consts::DEFAULT_GAS_PRICE,
+            FeeProfile::Generous => crate::consts::GENEROUS_GAS_PRICE,
+        };
+        price.parse().expect("invalid gas price")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gas_price_fees_round_up() {
+        let price: GasPrice = "0.25uscrt".parse().unwrap();
+        assert_eq!(price.denom(), "uscrt");
+        assert_eq!(price.to_string(), "0.25uscrt");
+        assert_eq!(price.fee_amount(200_000).unwrap(), 50_000);
+        assert_eq!(price.fee_amount(3).unwrap(), 1);
+
+        let price: GasPrice = "0.1uscrt".parse().unwrap();
+        assert_eq!(price.fee_amount(100_000).unwrap(), 10_000);
+        assert_eq!(price.fee_amount(100_001).unwrap(), 10_001);
+
+        assert_eq!(
+            "2uatom".parse::<GasPrice>().unwrap().fee_amount(7).unwrap(),
+            14
+        );
+        assert!("uscrt".parse::<GasPrice>().is_err());
+        assert!("0.25".parse::<GasPrice>().is_err());
+        assert!("0.2.5uscrt".parse::<GasPrice>().is_err());
+        assert!("0.25u".parse::<GasPrice>().is_err());
+        assert!("0.25 uscrt".parse::<GasPrice>().is_err());
+        assert!("0.25u$crt".parse::<GasPrice>().is_err());
+        assert!("0.25ibc/27394FB092D2ECCD".parse::<GasPrice>().is_ok());
+
+        let price: GasPrice = "1000000uscrt".parse().unwrap();
+        assert!(price.fee_amount(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn parse_event_coins() {
+        assert_eq!(
+            parse_coins(
+                "100uscrt,5ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            )
+            .unwrap(),
+            vec![
+                cosmwasm_std::coin(100, "uscrt"),
+                cosmwasm_std::coin(
+                    5,
+                    "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                ),
+            ]
+        );
+        assert!(parse_coins("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn pagination_windows_items() {
+        let page = Pagination::new(2, 3).apply((0..10).collect());
+        assert_eq!(page.items, vec![2, 3, 4]);
+        assert_eq!(page.total, 10);
+
+        let page = Pagination::new(8, 3).apply((0..10).collect());
+        assert_eq!(page.items, vec![8, 9]);
+
+        let page = Pagination::all().apply((0..10).collect::<Vec<_>>());
+        assert_eq!(page.items.len(), 10);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{client::Client, consts, Result};
 
-pub fn docker_run<F>(f: F) -> Result<()>
+pub fn docker_run<F>(chain_id: Option<&str>, f: F) -> Result<()>
 where
     F: FnOnce(&Client) -> Result<()> + std::panic::UnwindSafe,
 {
@@ -14,7 +14,7 @@ where
     ];
 
     cosmrs::dev::docker_run(docker_args, || {
-        let client = Client::init(consts::DEFAULT_RPC_HOST, consts::DEFAULT_RPC_PORT)?;
+        let client = Client::init(consts::DEFAULT_RPC_HOST, consts::DEFAULT_RPC_PORT, chain_id)?;
         client.wait_for_first_block()?;
         f(&client)
     })
@@ -5,10 +5,16 @@ pub(crate) mod crypto;
 
 mod docker;
 
-pub use account::{a, b, c, d, Account};
+pub use account::{a, b, c, d, Account, Multisig};
 pub use client::{
     tx::builder::*,
-    types::{CodeHash, CodeId, Contract, TxResponse},
+    types::{
+        AccountInfo, AccountKind, AtHeight, BalanceChange, BlockInfo, BondStatus, CodeHash, CodeId,
+        CodeInfo, Contract, ContractInfo, DecCoin, Delegation, DelegatorRewards, FeeProfile,
+        GasPrice, NodeInfo, Page, Pagination, Proposal, ProposalStatus, Redelegation,
+        RedelegationEntry, TallyResult, TxInfo, TxResponse, UnbondingDelegation, UnbondingEntry,
+        Validator, ValidatorRewards,
+    },
     Client,
 };
 pub use error::Error;
@@ -19,6 +25,7 @@ pub struct LocalSecret {
     spawn_docker: bool,
     rpc_host: String,
     rpc_port: u16,
+    chain_id: Option<String>,
 }
 
 pub fn env() -> LocalSecret {
@@ -26,6 +33,7 @@ pub fn env() -> LocalSecret {
         spawn_docker: true,
         rpc_host: consts::DEFAULT_RPC_HOST.to_owned(),
         rpc_port: consts::DEFAULT_RPC_PORT,
+        chain_id: None,
     }
 }
 
@@ -51,15 +59,22 @@ impl LocalSecret {
         self
     }
 
+    /// Specify the chain id the node is expected to run, checked before the first broadcast.
+    /// Default: detected from the node
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
     /// (Conditionally) Spawn a docker container, connect the RPC client and pass it to the session function.
     pub fn run<F>(&self, f: F) -> Result<()>
     where
         F: FnOnce(&Client) -> Result<()> + std::panic::UnwindSafe,
     {
         if self.spawn_docker {
-            docker::docker_run(f)
+            docker::docker_run(self.chain_id.as_deref(), f)
         } else {
-            let client = Client::init(&self.rpc_host, self.rpc_port)?;
+            let client = Client::init(&self.rpc_host, self.rpc_port, self.chain_id.as_deref())?;
             f(&client)
         }
     }
@@ -75,16 +90,50 @@ pub mod error {
     pub enum Error {
         #[error("Failed to initialise tokio runtime: {0}")]
         Runtime(std::io::Error),
+        #[error("Invalid chain id: {0}")]
+        InvalidChainId(String),
+        #[error("Invalid gas price: {0}")]
+        InvalidGasPrice(String),
+        #[error("Fee for {0} gas at {1} exceeds the maximum coin amount")]
+        FeeOverflow(u64, String),
+        #[error("Total amount of {0} exceeds the maximum coin amount")]
+        AmountOverflow(String),
+        #[error("Invalid gas multiplier {0}, expected a finite value of at least 1")]
+        InvalidGasMultiplier(f64),
+        #[error("Chain id mismatch - expected {0} but the node runs {1}")]
+        ChainIdMismatch(String, String),
         #[error("RPC failure: {0}")]
         Rpc(#[from] cosmrs::rpc::Error),
         #[error("Failed to read contract file: {0} - {1}")]
         ContractFile(String, std::io::Error),
         #[error("Contract with label {0} already deployed")]
         ContractLabelExists(String),
+        #[error("Contract not found at address: {0}")]
+        ContractNotFound(cosmwasm_std::HumanAddr),
         #[error("Contract Info not found for code id: {0}")]
         ContractInfoNotFound(crate::CodeId),
         #[error("Timed out waiting for first block after {0} seconds")]
         FirstBlockTimeout(u128),
+        #[error("Invalid transaction hash: {0}")]
+        InvalidTxHash(String),
+        #[error("Invalid signed tx: {0}")]
+        InvalidSignedTx(String),
+        #[error("Message {0} cannot be signed in legacy Amino JSON mode")]
+        UnsupportedAminoMsg(String),
+        #[error("Multisig error: {0}")]
+        Multisig(String),
+        #[error("Invalid block height: {0}")]
+        InvalidHeight(u64),
+        #[error("Chain stalled at height {0}, no new block for {1} seconds")]
+        ChainStalled(u64, u64),
+        #[error("Tx {0} not included in a block after {1} seconds")]
+        TxTimeout(String, u64),
+        #[error("Validator {0} not found")]
+        ValidatorNotFound(String),
+        #[error("Proposal {0} not found")]
+        ProposalNotFound(u64),
+        #[error("Proposal {0} is not in its voting period")]
+        ProposalNotInVotingPeriod(u64),
         #[error("ABCI Query failed: {0}")]
         AbciQuery(String),
         #[error("Decoding protobuf response failed: {0}")]
@@ -93,12 +142,16 @@ pub mod error {
         CosmwWasm(#[from] cosmwasm_std::StdError),
         #[error("Account {0} not found")]
         AccountNotFound(cosmwasm_std::HumanAddr),
+        #[error("Unsupported account type: {0}")]
+        UnsupportedAccountType(String),
         #[error("CosmRs error: {0}")]
         CosmRs(#[from] cosmrs::ErrorReport),
         #[error("Broadcast error - check tx failed: {0}")]
         BroadcastTxCheck(String),
         #[error("Broadcast error - deliver tx failed: {0}")]
         BroadcastTxDeliver(String),
+        #[error("Batch of {0} messages got {1} message responses")]
+        BatchResponseMismatch(usize, usize),
         #[error("Failed to parse message response: {0}")]
         ParseMsgResponse(#[from] ParseError),
         #[error("Parsing TEE cert failed: {0}")]
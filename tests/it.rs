@@ -6,6 +6,50 @@ fn test_contract() {
 fn test_contract_session(client: &localsecret::Client) -> localsecret::Result<()> {
     let a = localsecret::a();
 
+    let node_info = client.node_info()?;
+    assert_eq!(node_info.chain_id, "secretdev-1");
+    assert!(!node_info.catching_up);
+
+    let uscrt = client.query_balance(a.human_address().as_str(), "uscrt")?;
+    assert!(!uscrt.amount.is_zero());
+    assert!(client
+        .query_all_balances(a.human_address().as_str())?
+        .contains(&uscrt));
+    assert!(client.query_supply_of("uscrt")?.amount >= uscrt.amount);
+
+    use cosmrs::proto::cosmos::mint::v1beta1::{QueryInflationRequest, QueryInflationResponse};
+    let inflation: QueryInflationResponse = client.abci_query(
+        "/cosmos.mint.v1beta1.Query/Inflation",
+        QueryInflationRequest {},
+    )?;
+    assert!(!inflation.inflation.is_empty());
+
+    use cosmrs::proto::cosmos::bank::v1beta1::{QueryBalanceRequest, QueryBalanceResponse};
+    use prost::Message;
+    let balance_req = QueryBalanceRequest {
+        address: a.human_address().to_string(),
+        denom: "uscrt".to_owned(),
+    };
+    let any_req = cosmrs::Any {
+        type_url: "/cosmos.bank.v1beta1.QueryBalanceRequest".to_owned(),
+        value: balance_req.encode_to_vec(),
+    };
+    let balance: QueryBalanceResponse =
+        client.abci_query("/cosmos.bank.v1beta1.Query/Balance", balance_req)?;
+    let any_balance: QueryBalanceResponse =
+        client.abci_query("/cosmos.bank.v1beta1.Query/Balance", any_req)?;
+    assert!(balance.balance.is_some());
+    assert_eq!(any_balance, balance);
+
+    let validators = client.query_validators()?;
+    assert!(validators
+        .iter()
+        .any(|v| v.status == localsecret::BondStatus::Bonded));
+
+    let account = client.query_account(a.human_address().as_str())?;
+    assert_eq!(account.address, a.human_address());
+    assert_eq!(account.kind, localsecret::AccountKind::Base);
+
     let code_id = client
         .tx()
         .upload("target/test_contract.wasm.gz")
@@ -21,10 +65,28 @@ fn test_contract_session(client: &localsecret::Client) -> localsecret::Result<()
             },
             code_id,
         )
+        .label("greeter")
         .from(&a)
         .broadcast()?
         .into_inner();
 
+    let codes = client.list_codes(localsecret::Pagination::all())?;
+    assert!(codes.items.iter().any(|ci| ci.code_id == code_id));
+
+    let contracts = client.contracts_by_code(code_id, localsecret::Pagination::all())?;
+    assert_eq!(contracts.total, 1);
+    assert_eq!(
+        contracts.items[0].contract.human_address(),
+        contract.human_address()
+    );
+
+    let labelled = client.contract_by_label("greeter")?.unwrap();
+    assert_eq!(labelled.human_address(), contract.human_address());
+    assert!(client.contract_by_label("missing")?.is_none());
+
+    let attached = client.contract_at(contract.human_address().as_str())?;
+    assert_eq!(attached.code_hash_string(), contract.code_hash_string());
+
     let greeting: test_contract::QueryAnswer = client.query_contract(
         &test_contract::QueryMsg::Greet {
             user: a.human_address(),
@@ -38,7 +100,7 @@ fn test_contract_session(client: &localsecret::Client) -> localsecret::Result<()
         greeting.greet
     );
 
-    let answer: test_contract::HandleAnswer = client
+    let tx = client
         .tx()
         .execute(
             &test_contract::HandleMsg::ModifyGreeting {
@@ -47,8 +109,35 @@ fn test_contract_session(client: &localsecret::Client) -> localsecret::Result<()
             &contract,
         )
         .from(&a)
-        .broadcast()?
-        .into_inner();
+        .broadcast()?;
+
+    let committed = client.tx_by_hash(&tx.hash)?;
+    assert!(committed.is_ok());
+    assert_eq!(committed.height, tx.height);
+
+    let block = client.block(tx.height)?;
+    assert!(block.num_txs >= 1);
+    let next = client.wait_blocks(1)?;
+    assert!(next.height > tx.height);
+    assert!(next.time >= block.time);
+
+    let found = client.search_txs(&format!("tx.height={}", tx.height))?;
+    assert!(found.iter().any(|t| t.hash == tx.hash));
+
+    let greeting: test_contract::QueryAnswer = client.at_height(tx.height - 1).query_contract(
+        &test_contract::QueryMsg::Greet {
+            user: a.human_address(),
+        },
+        &contract,
+        &a,
+    )?;
+
+    assert_eq!(
+        test_contract::query::greet_user("YO", &a.human_address()),
+        greeting.greet
+    );
+
+    let answer: test_contract::HandleAnswer = tx.into_inner();
 
     assert_eq!(
         answer,
@@ -71,5 +160,371 @@ fn test_contract_session(client: &localsecret::Client) -> localsecret::Result<()
         greeting.greet
     );
 
+    let greet_msg = test_contract::QueryMsg::Greet {
+        user: a.human_address(),
+    };
+    let json = client.query_contract_json(&greet_msg, &contract, &a)?;
+    assert_eq!(json["greet"], serde_json::json!(greeting.greet));
+    let raw = client.query_contract_raw(&greet_msg, &contract, &a)?;
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&raw)?, json);
+
+    let responses = client
+        .tx()
+        .batch()
+        .add(client.tx().execute::<_, test_contract::HandleAnswer>(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Bonjour".to_string(),
+            },
+            &contract,
+        ))
+        .add(client.tx().execute::<_, test_contract::HandleAnswer>(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Ciao".to_string(),
+            },
+            &contract,
+        ))
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+
+    let answers = responses
+        .iter()
+        .map(|res| res.execute_response::<test_contract::HandleAnswer>())
+        .collect::<localsecret::Result<Vec<_>>>()?;
+    assert_eq!(answers[0].as_ref().unwrap().new_greeting, "Bonjour");
+    assert_eq!(answers[1].as_ref().unwrap().old_greeting, "Bonjour");
+
+    let proposal_id = client
+        .tx()
+        .submit_proposal(localsecret::ProposalContent::Text {
+            title: "Greetings".to_string(),
+            description: "Say hello".to_string(),
+        })
+        .initial_deposit(10_000_000)
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+
+    client
+        .tx()
+        .vote(proposal_id, localsecret::VoteOption::Yes)
+        .from(&a)
+        .broadcast()?;
+
+    let proposal = client.query_proposal(proposal_id)?;
+    assert_eq!(proposal.status, localsecret::ProposalStatus::VotingPeriod);
+
+    let b = localsecret::b();
+    let b_before = client.query_uscrt_balance(b.human_address().as_str())?;
+    let sent = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1_000, "uscrt")])
+        .from(&a)
+        .broadcast()?;
+    assert!(sent
+        .balance_changes()?
+        .contains(&localsecret::BalanceChange::Received {
+            address: b.human_address(),
+            amount: vec![cosmwasm_std::coin(1_000, "uscrt")],
+        }));
+    assert_eq!(
+        client.query_uscrt_balance(b.human_address().as_str())?,
+        b_before + cosmwasm_std::Uint128(1_000)
+    );
+
+    let validator = validators[0].operator_address.clone();
+    client
+        .tx()
+        .delegate(&validator, 1_000_000)
+        .from(&b)
+        .broadcast()?;
+    assert!(client
+        .query_delegations(b.human_address().as_str())?
+        .iter()
+        .any(|d| d.validator == validator));
+    client
+        .tx()
+        .withdraw_rewards(&validator)
+        .from(&b)
+        .broadcast()?;
+    let completion_time = client
+        .tx()
+        .undelegate(&validator, 500_000)
+        .from(&b)
+        .broadcast()?
+        .into_inner();
+    assert!(completion_time > std::time::SystemTime::now());
+    assert_eq!(
+        client.query_unbonding_delegations(b.human_address().as_str())?[0].entries[0]
+            .completion_time,
+        completion_time
+    );
+
+    let auto: test_contract::HandleAnswer = client
+        .tx()
+        .execute(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Hallo".to_string(),
+            },
+            &contract,
+        )
+        .gas_auto(1.3)
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+    assert_eq!(auto.old_greeting, "Ciao");
+
+    let cheap = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .fee_profile(localsecret::FeeProfile::Cheap)
+        .gas_limit(100_000)
+        .from(&a)
+        .broadcast()?;
+    assert!(cheap
+        .balance_changes()?
+        .contains(&localsecret::BalanceChange::Spent {
+            address: a.human_address(),
+            amount: vec![cosmwasm_std::coin(1_250, "uscrt")],
+        }));
+
+    let c = localsecret::c();
+    let simulated = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .fee_payer(&c)
+        .from(&a)
+        .simulate()?;
+    assert!(simulated.gas_used > 0);
+    let paid = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .fee_payer(&c)
+        .gas_auto(1.3)
+        .from(&a)
+        .broadcast()?;
+    assert!(paid.balance_changes()?.iter().any(|change| matches!(
+        change,
+        localsecret::BalanceChange::Spent { address, .. } if *address == c.human_address()
+    )));
+
+    // without a grant from c the fee cannot be deducted, but the tx is well formed
+    let ungranted = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .fee_granter(&c)
+        .gas_auto(1.3)
+        .from(&a)
+        .broadcast();
+    assert!(matches!(
+        ungranted,
+        Err(localsecret::Error::BroadcastTxDeliver(log)) if log.contains("fee-grant")
+    ));
+
+    let pending = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .from(&a)
+        .broadcast_sync()?;
+    let hash = pending.hash().to_owned();
+    let included = pending.wait(std::time::Duration::from_secs(30))?;
+    assert_eq!(included.hash, hash);
+    let waited = client.wait_for_tx(&hash, std::time::Duration::from_secs(30))?;
+    assert_eq!(waited.height, included.height);
+    assert_eq!(waited.response.map(|msgs| msgs.len()), Some(1));
+
+    let pipelined = (0..20)
+        .map(|_| {
+            client
+                .tx()
+                .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+                .from(&a)
+                .broadcast_async()
+        })
+        .collect::<localsecret::Result<Vec<_>>>()?;
+    for pending in pipelined {
+        pending.wait(std::time::Duration::from_secs(30))?;
+    }
+
+    let memo = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .memo("hello memo")
+        .no_timeout()
+        .from(&a)
+        .broadcast()?;
+    assert_eq!(client.tx_by_hash(&memo.hash)?.memo, "hello memo");
+    assert!(client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .timeout_height(memo.height)
+        .from(&a)
+        .broadcast()
+        .is_err());
+
+    let signed = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .from(&a)
+        .sign()?;
+    let exported = signed.to_json();
+    let imported = localsecret::SignedTx::from_json(&exported)?;
+    let replayed = client.broadcast_raw(&imported)?;
+    assert_eq!(replayed.hash, signed.hash());
+    assert!(client.broadcast_raw(&imported).is_err());
+
+    let amino: test_contract::HandleAnswer = client
+        .tx()
+        .execute(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Hallo & <Servus>".to_string(),
+            },
+            &contract,
+        )
+        .memo("signed as amino json")
+        .sign_mode(localsecret::SignMode::LegacyAminoJson)
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+    assert_eq!(amino.old_greeting, "Hallo");
+    client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .sign_mode(localsecret::SignMode::LegacyAminoJson)
+        .from(&a)
+        .broadcast()?;
+    let amino_code_id = client
+        .tx()
+        .upload("target/test_contract.wasm.gz")
+        .sign_mode(localsecret::SignMode::LegacyAminoJson)
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+    let amino_contract = client
+        .tx()
+        .init(
+            &test_contract::InitMsg {
+                greeting: "Moin".to_string(),
+            },
+            amino_code_id,
+        )
+        .label("amino-greeter")
+        .sign_mode(localsecret::SignMode::LegacyAminoJson)
+        .from(&a)
+        .broadcast()?
+        .into_inner();
+    let greeting: test_contract::QueryAnswer =
+        client.query_contract(&greet_msg, &amino_contract, &a)?;
+    assert_eq!(
+        test_contract::query::greet_user("Moin", &a.human_address()),
+        greeting.greet
+    );
+    client
+        .tx()
+        .execute::<_, test_contract::HandleAnswer>(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Hallo".to_string(),
+            },
+            &contract,
+        )
+        .from(&a)
+        .broadcast()?;
+
+    let multisig = localsecret::Multisig::new(
+        2,
+        &[
+            a.public_key(),
+            localsecret::c().public_key(),
+            localsecret::d().public_key(),
+        ],
+    )?;
+    client
+        .tx()
+        .send(
+            multisig.human_address(),
+            vec![cosmwasm_std::coin(1_000_000, "uscrt")],
+        )
+        .from(&a)
+        .broadcast()?;
+    let unsigned = client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1_000, "uscrt")])
+        .from_multisig(&multisig)
+        .unsigned()?;
+    // each member signs its own copy, as it would on another machine
+    let exported = unsigned.to_json()?;
+    let partial = [
+        localsecret::UnsignedTx::from_json(&exported)?
+            .sign_partial(&localsecret::d())?
+            .to_json(),
+        localsecret::UnsignedTx::from_json(&exported)?
+            .sign_partial(&a)?
+            .to_json(),
+    ]
+    .iter()
+    .map(|json| localsecret::PartialSignature::from_json(json))
+    .collect::<localsecret::Result<Vec<_>>>()?;
+    assert!(unsigned.sign_partial(&b).is_err());
+    assert!(unsigned.aggregate(&partial[..1]).is_err());
+    let multisig_tx = client.broadcast_raw(&unsigned.aggregate(&partial)?)?;
+    assert!(multisig_tx
+        .balance_changes()?
+        .contains(&localsecret::BalanceChange::Received {
+            address: b.human_address(),
+            amount: vec![cosmwasm_std::coin(1_000, "uscrt")],
+        }));
+
+    // an abandoned unsigned tx does not hold up the next one
+    client
+        .tx()
+        .send(b.human_address(), vec![cosmwasm_std::coin(1, "uscrt")])
+        .from_multisig(&multisig)
+        .unsigned()?;
+    let unsigned = client
+        .tx()
+        .execute::<_, test_contract::HandleAnswer>(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Servus".to_string(),
+            },
+            &contract,
+        )
+        .from_multisig(&multisig.clone().with_encryption_account(&b))
+        .unsigned()?;
+    let partial = [
+        unsigned.sign_partial(&a)?,
+        unsigned.sign_partial(&localsecret::c())?,
+    ];
+    let signed = unsigned.aggregate(&partial)?;
+    let multisig_exec = client.broadcast_raw(&signed)?.into_inner();
+    let answer: test_contract::HandleAnswer =
+        client.decrypt_msg_response(&signed, 0, &multisig_exec[0], &b)?;
+    assert_eq!(answer.old_greeting, "Hallo");
+    assert!(client
+        .decrypt_msg_response::<test_contract::HandleAnswer>(&signed, 0, &multisig_exec[0], &a)
+        .is_err());
+    let greeting: test_contract::QueryAnswer = client.query_contract(&greet_msg, &contract, &a)?;
+    assert_eq!(
+        test_contract::query::greet_user("Servus", &a.human_address()),
+        greeting.greet
+    );
+
+    let dry_run = client
+        .tx()
+        .execute::<_, test_contract::HandleAnswer>(
+            &test_contract::HandleMsg::ModifyGreeting {
+                greeting: "Hej".to_string(),
+            },
+            &contract,
+        )
+        .from(&a)
+        .simulate()?;
+    assert!(dry_run.gas_used > 0);
+    assert_eq!(dry_run.into_inner().old_greeting, "Servus");
+    let greeting: test_contract::QueryAnswer = client.query_contract(&greet_msg, &contract, &a)?;
+    assert_eq!(
+        test_contract::query::greet_user("Servus", &a.human_address()),
+        greeting.greet
+    );
+
     Ok(())
 }